@@ -1,9 +1,22 @@
 use std::collections::{HashSet, HashMap};
+use std::fs;
 use std::hash::Hash;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::ExitCode;
 
 trait EmojiClassifier {
-    fn classify(&self, emoji: char) -> EmojiCategory;
+    fn classify(&self, emoji: &str) -> EmojiCategory;
     fn get_complexity_weight(&self, category: &EmojiCategory) -> u32;
+    /// Number of emojis an attacker has to choose from at each position.
+    fn alphabet_size(&self) -> usize;
+    /// Where the emoji sits in picker order, to spot neighbours picked together.
+    fn picker_position(&self, emoji: &str) -> Option<usize>;
+    /// Every emoji the classifier places in `category`, in picker order.
+    fn emojis_in(&self, category: &EmojiCategory) -> Vec<&str>;
+    /// Whether the symbol shows as an emoji rather than text: its base is emoji-presentation by
+    /// default or is followed by FE0F.
+    fn is_emoji_presentation(&self, emoji: &str) -> bool;
 }
 
 trait PasswordValidator<T> {
@@ -11,100 +24,467 @@ trait PasswordValidator<T> {
 }
 
 trait PatternAnalyzer {
-    fn analyze_patterns(&self, emojis: &[char]) -> PatternScore;
+    fn analyze_patterns(&self, emojis: &[&str], classifier: &dyn EmojiClassifier) -> PatternScore;
 }
 
+// Extended grapheme cluster segmentation (Unicode UAX #29), so that a ZWJ family, a flag or an
+// emoji with a skin tone or variation selector counts as the single symbol the user sees.
+mod grapheme {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Property {
+        CR,
+        LF,
+        Control,
+        Extend,
+        Zwj,
+        RegionalIndicator,
+        SpacingMark,
+        L,
+        V,
+        T,
+        Lv,
+        Lvt,
+        Pictographic,
+        Other,
+    }
+
+    const EXTEND: &[(u32, u32)] = &[
+        (0x0300, 0x036F), (0x0483, 0x0489), (0x0591, 0x05BD), (0x0610, 0x061A),
+        (0x064B, 0x065F), (0x0670, 0x0670), (0x06D6, 0x06DC), (0x0900, 0x0902),
+        (0x093C, 0x093C), (0x0941, 0x0948), (0x094D, 0x094D), (0x0E31, 0x0E31),
+        (0x0E34, 0x0E3A), (0x0E47, 0x0E4E), (0x1AB0, 0x1AFF), (0x1DC0, 0x1DFF),
+        (0x200C, 0x200C), (0x20D0, 0x20FF), (0x302A, 0x302F), (0x3099, 0x309A),
+        (0xFE00, 0xFE0F), (0xFE20, 0xFE2F), (0x1F3FB, 0x1F3FF), (0xE0020, 0xE007F),
+        (0xE0100, 0xE01EF),
+    ];
+
+    const SPACING_MARK: &[(u32, u32)] = &[
+        (0x0903, 0x0903), (0x093B, 0x093B), (0x093E, 0x0940), (0x0949, 0x094C),
+        (0x094E, 0x094F), (0x0E33, 0x0E33),
+    ];
+
+    const PICTOGRAPHIC: &[(u32, u32)] = &[
+        (0x00A9, 0x00A9), (0x00AE, 0x00AE), (0x203C, 0x203C), (0x2049, 0x2049),
+        (0x2122, 0x2122), (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA),
+        (0x231A, 0x231B), (0x2328, 0x2328), (0x2388, 0x2388), (0x23CF, 0x23CF),
+        (0x23E9, 0x23F3), (0x23F8, 0x23FA), (0x24C2, 0x24C2), (0x25AA, 0x25AB),
+        (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FE), (0x2600, 0x27BF),
+        (0x2934, 0x2935), (0x2B05, 0x2B07), (0x2B1B, 0x2B1C), (0x2B50, 0x2B50),
+        (0x2B55, 0x2B55), (0x3030, 0x3030), (0x303D, 0x303D), (0x3297, 0x3297),
+        (0x3299, 0x3299), (0x1F000, 0x1F0FF), (0x1F10D, 0x1F10F), (0x1F12F, 0x1F12F),
+        (0x1F16C, 0x1F171), (0x1F17E, 0x1F17F), (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A),
+        (0x1F1AD, 0x1F1E5), (0x1F201, 0x1F20F), (0x1F21A, 0x1F21A), (0x1F22F, 0x1F22F),
+        (0x1F232, 0x1F23A), (0x1F23C, 0x1F23F), (0x1F249, 0x1F3FA), (0x1F400, 0x1F53D),
+        (0x1F546, 0x1F64F), (0x1F680, 0x1F6FF), (0x1F774, 0x1F77F), (0x1F7D5, 0x1F7FF),
+        (0x1F80C, 0x1F80F), (0x1F848, 0x1F84F), (0x1F85A, 0x1F85F), (0x1F888, 0x1F88F),
+        (0x1F8AE, 0x1F8FF), (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1FAFF),
+        (0x1FC00, 0x1FFFD),
+    ];
+
+    fn in_ranges(cp: u32, ranges: &[(u32, u32)]) -> bool {
+        ranges
+            .binary_search_by(|&(start, end)| {
+                if end < cp {
+                    std::cmp::Ordering::Less
+                } else if start > cp {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    fn property(c: char) -> Property {
+        let cp = c as u32;
+        match cp {
+            0x0D => Property::CR,
+            0x0A => Property::LF,
+            0x200D => Property::Zwj,
+            0x1F1E6..=0x1F1FF => Property::RegionalIndicator,
+            0x1100..=0x115F | 0xA960..=0xA97C => Property::L,
+            0x1160..=0x11A7 | 0xD7B0..=0xD7C6 => Property::V,
+            0x11A8..=0x11FF | 0xD7CB..=0xD7FB => Property::T,
+            0xAC00..=0xD7A3 if (cp - 0xAC00).is_multiple_of(28) => Property::Lv,
+            0xAC00..=0xD7A3 => Property::Lvt,
+            0x2028 | 0x2029 => Property::Control,
+            _ if c.is_control() => Property::Control,
+            _ if in_ranges(cp, EXTEND) => Property::Extend,
+            _ if in_ranges(cp, SPACING_MARK) => Property::SpacingMark,
+            _ if in_ranges(cp, PICTOGRAPHIC) => Property::Pictographic,
+            _ => Property::Other,
+        }
+    }
+
+    pub struct Graphemes<'a> {
+        rest: &'a str,
+    }
+
+    pub fn graphemes(text: &str) -> Graphemes<'_> {
+        Graphemes { rest: text }
+    }
+
+    impl<'a> Iterator for Graphemes<'a> {
+        type Item = &'a str;
+
+        fn next(&mut self) -> Option<&'a str> {
+            use Property::*;
+
+            let mut chars = self.rest.char_indices();
+            let (_, first) = chars.next()?;
+
+            let mut prev = property(first);
+            // State for GB11 (emoji ZWJ sequences) and GB12/13 (flag pairs).
+            let mut in_pictographic = prev == Pictographic;
+            let mut regional_run = usize::from(prev == RegionalIndicator);
+            let mut end = self.rest.len();
+
+            for (i, c) in chars {
+                let next = property(c);
+                let join = match (prev, next) {
+                    (CR, LF) => true,
+                    (CR | LF | Control, _) | (_, CR | LF | Control) => false,
+                    (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => true,
+                    (_, Extend | Zwj | SpacingMark) => true,
+                    (Zwj, Pictographic) => in_pictographic,
+                    (RegionalIndicator, RegionalIndicator) => regional_run % 2 == 1,
+                    _ => false,
+                };
+                if !join {
+                    end = i;
+                    break;
+                }
+
+                in_pictographic = match next {
+                    Pictographic => true,
+                    Extend | Zwj => in_pictographic && prev != Zwj,
+                    _ => false,
+                };
+                regional_run = if next == RegionalIndicator { regional_run + 1 } else { 0 };
+                prev = next;
+            }
+
+            let (cluster, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            Some(cluster)
+        }
+    }
+}
+
+// One category per Unicode emoji group.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum EmojiCategory {
     Face,
-    Object, 
+    People,
+    Component,
     Nature,
-    Animal,
     Food,
+    Travel,
+    Activity,
+    Object,
+    Symbol,
+    Flag,
     Unknown,
 }
 
+impl EmojiCategory {
+    const ALL: [EmojiCategory; 11] = [
+        EmojiCategory::Face, EmojiCategory::People, EmojiCategory::Component,
+        EmojiCategory::Nature, EmojiCategory::Food, EmojiCategory::Travel,
+        EmojiCategory::Activity, EmojiCategory::Object, EmojiCategory::Symbol,
+        EmojiCategory::Flag, EmojiCategory::Unknown,
+    ];
+
+    fn from_group(group: &str) -> Self {
+        match group {
+            "Smileys & Emotion" => EmojiCategory::Face,
+            "People & Body" => EmojiCategory::People,
+            "Component" => EmojiCategory::Component,
+            "Animals & Nature" => EmojiCategory::Nature,
+            "Food & Drink" => EmojiCategory::Food,
+            "Travel & Places" => EmojiCategory::Travel,
+            "Activities" => EmojiCategory::Activity,
+            "Objects" => EmojiCategory::Object,
+            "Symbols" => EmojiCategory::Symbol,
+            "Flags" => EmojiCategory::Flag,
+            _ => EmojiCategory::Unknown,
+        }
+    }
+
+    fn default_weight(&self) -> u32 {
+        match self {
+            EmojiCategory::Face | EmojiCategory::Component | EmojiCategory::Unknown => 1,
+            EmojiCategory::People | EmojiCategory::Food => 2,
+            EmojiCategory::Nature | EmojiCategory::Symbol => 3,
+            EmojiCategory::Travel | EmojiCategory::Activity | EmojiCategory::Flag => 4,
+            EmojiCategory::Object => 5,
+        }
+    }
+}
+
+const EMOJI_TEST_DATA: &str = include_str!("data/emoji-test.txt");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmojiInfo {
+    emoji: String,
+    position: usize,
+    name: String,
+    group: String,
+    subgroup: String,
+    category: EmojiCategory,
+}
+
 #[derive(Clone)] 
 struct AdvancedEmojiClassifier {
-    classification_map: HashMap<char, EmojiCategory>,
+    database: HashMap<String, EmojiInfo>,
     complexity_weights: HashMap<EmojiCategory, u32>,
 }
 
 impl AdvancedEmojiClassifier {
     fn new() -> Self {
-        let mut map = HashMap::new();
-        let mut weights = HashMap::new();
-
-        let face_emojis = ['😀', '😎', '🤔', '😊', '🙄', '😱', '🤗', '😴'];
-        let object_emojis = ['🔑', '🎯', '⚡', '🔒', '💎', '🛡', '⭐', '🎪'];
-        let nature_emojis = ['🌟', '🌈', '🔥', '🌙', '☀', '🌊', '🌸', '🍃'];
-        let animal_emojis = ['🦄', '🐱', '🦊', '🐺', '🦅', '🐢', '🦋', '🐝'];
-        let food_emojis = ['🍎', '🍕', '🎂', '🍯', '🥑', '🍓', '🥨', '🍜'];
-
-        for &emoji in &face_emojis { map.insert(emoji, EmojiCategory::Face); }
-        for &emoji in &object_emojis { map.insert(emoji, EmojiCategory::Object); }
-        for &emoji in &nature_emojis { map.insert(emoji, EmojiCategory::Nature); }
-        for &emoji in &animal_emojis { map.insert(emoji, EmojiCategory::Animal); }
-        for &emoji in &food_emojis { map.insert(emoji, EmojiCategory::Food); }
-
-        weights.insert(EmojiCategory::Face, 1);
-        weights.insert(EmojiCategory::Food, 2);
-        weights.insert(EmojiCategory::Animal, 3);
-        weights.insert(EmojiCategory::Nature, 4);
-        weights.insert(EmojiCategory::Object, 5);
-        weights.insert(EmojiCategory::Unknown, 1);
+        Self::from_emoji_test(EMOJI_TEST_DATA)
+    }
+
+    // Parses the Unicode emoji-test.txt format:
+    // `1F44B 1F3FB ; fully-qualified # 👋🏻 E1.0 waving hand: light skin tone`
+    fn from_emoji_test(data: &str) -> Self {
+        let mut database = HashMap::new();
+        let mut group = String::new();
+        let mut subgroup = String::new();
+
+        for line in data.lines() {
+            if let Some(name) = line.strip_prefix("# group:") {
+                group = name.trim().to_string();
+                subgroup.clear();
+                continue;
+            }
+            if let Some(name) = line.strip_prefix("# subgroup:") {
+                subgroup = name.trim().to_string();
+                continue;
+            }
+
+            let Some((code_points, rest)) = line.split_once(';') else { continue };
+            if line.starts_with('#') {
+                continue;
+            }
+
+            let emoji: Option<String> = code_points
+                .split_whitespace()
+                .map(|hex| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32))
+                .collect();
+            let Some(emoji) = emoji.filter(|e| !e.is_empty()) else { continue };
+
+            // Comment: "# <emoji> E<version> <name>"
+            let name = rest
+                .split_once('#')
+                .map(|(_, comment)| comment.trim().splitn(3, ' ').nth(2).unwrap_or("").to_string())
+                .unwrap_or_default();
+
+            let position = database.len();
+            database.entry(emoji_key(&emoji)).or_insert(EmojiInfo {
+                emoji: emoji.clone(),
+                position,
+                name,
+                group: group.clone(),
+                subgroup: subgroup.clone(),
+                category: EmojiCategory::from_group(&group),
+            });
+        }
+
+        let complexity_weights = EmojiCategory::ALL.iter()
+            .map(|category| (category.clone(), category.default_weight()))
+            .collect();
 
         Self {
-            classification_map: map,
-            complexity_weights: weights,
+            database,
+            complexity_weights,
         }
     }
+
+    fn with_weight(mut self, category: EmojiCategory, weight: u32) -> Self {
+        self.complexity_weights.insert(category, weight);
+        self
+    }
+
+    fn info(&self, emoji: &str) -> Option<&EmojiInfo> {
+        self.database.get(&emoji_key(emoji)).or_else(|| {
+            // Sequences missing from the data (odd ZWJ combinations) fall back to their base emoji.
+            let base = emoji.chars().next()?;
+            self.database.get(&emoji_key(&base.to_string()))
+        })
+    }
+
+    fn emoji_count(&self) -> usize {
+        self.database.len()
+    }
+}
+
+// Text/emoji presentation selectors don't change the emoji, so '🛡' and '🛡️' share a key.
+fn emoji_key(emoji: &str) -> String {
+    emoji.chars().filter(|&c| c != '\u{FE0E}' && c != '\u{FE0F}').collect()
 }
 
 impl EmojiClassifier for AdvancedEmojiClassifier {
-    fn classify(&self, emoji: char) -> EmojiCategory {
-        self.classification_map.get(&emoji)
-            .cloned()
+    fn classify(&self, emoji: &str) -> EmojiCategory {
+        self.info(emoji)
+            .map(|info| info.category.clone())
             .unwrap_or(EmojiCategory::Unknown)
     }
 
     fn get_complexity_weight(&self, category: &EmojiCategory) -> u32 {
         *self.complexity_weights.get(category).unwrap_or(&1)
     }
+
+    fn alphabet_size(&self) -> usize {
+        self.database.len()
+    }
+
+    fn picker_position(&self, emoji: &str) -> Option<usize> {
+        self.database.get(&emoji_key(emoji)).map(|info| info.position)
+    }
+
+    fn emojis_in(&self, category: &EmojiCategory) -> Vec<&str> {
+        let mut infos: Vec<&EmojiInfo> = self.database.values()
+            .filter(|info| info.category == *category)
+            .collect();
+        infos.sort_by_key(|info| info.position);
+        infos.into_iter().map(|info| info.emoji.as_str()).collect()
+    }
+
+    // The fully-qualified form puts FE0F right after a base that defaults to text, as in "©️".
+    fn is_emoji_presentation(&self, emoji: &str) -> bool {
+        let Some(info) = self.info(emoji) else { return false };
+        match emoji.chars().nth(1) {
+            Some('\u{FE0F}') => true,
+            Some('\u{FE0E}') => false,
+            _ => info.emoji.chars().nth(1) != Some('\u{FE0F}'),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PatternKind {
+    /// The same block repeated back to back; period 1 is a run of one emoji.
+    Repetition { period: usize },
+    Palindrome,
+    CategoryRun(EmojiCategory),
+    PickerRow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PatternFinding {
+    kind: PatternKind,
+    start: usize,
+    length: usize,
+    penalty: u32,
 }
 
 struct PatternScore {
     alternating_bonus: u32,
-    repetition_penalty: u32,
+    pattern_penalty: u32,
     sequence_bonus: u32,
+    findings: Vec<PatternFinding>,
 }
 
-#[derive(Clone)] 
+#[derive(Clone)]
 struct AdvancedPatternAnalyzer;
 
-impl PatternAnalyzer for AdvancedPatternAnalyzer {
-    fn analyze_patterns(&self, emojis: &[char]) -> PatternScore {
-        let mut alternating_bonus = 0;
-        let mut repetition_penalty = 0;
-        let mut sequence_bonus = 0;
+impl AdvancedPatternAnalyzer {
+    // Shortest period first, so "😀😀😀😀" is one period-1 run rather than also a period-2 one.
+    fn repetitions(emojis: &[&str]) -> Vec<PatternFinding> {
+        let mut findings: Vec<PatternFinding> = Vec::new();
 
-        if emojis.len() >= 4 {
+        for period in 1..=emojis.len() / 2 {
+            let mut i = 0;
+            while i + period < emojis.len() {
+                let start = i;
+                while i + period < emojis.len() && emojis[i] == emojis[i + period] {
+                    i += 1;
+                }
 
-            let mut alternating_count = 0;
-            for window in emojis.windows(3) {
-                if window[0] != window[1] && window[1] != window[2] && window[0] != window[2] {
-                    alternating_count += 1;
+                let length = i - start + period;
+                let covered = findings.iter()
+                    .any(|f| f.start <= start && start + length <= f.start + f.length);
+                if i - start >= period && !covered {
+                    findings.push(PatternFinding {
+                        kind: PatternKind::Repetition { period },
+                        start,
+                        length,
+                        penalty: (length - period) as u32 * 5,
+                    });
                 }
+                i = i.max(start + 1);
             }
-            alternating_bonus = (alternating_count * 5).min(15);
+        }
 
-            let mut consecutive_same = 0;
-            for window in emojis.windows(2) {
-                if window[0] == window[1] {
-                    consecutive_same += 1;
+        findings
+    }
+
+    // Maximal mirrored stretches of four or more that aren't just a repetition.
+    fn palindromes(emojis: &[&str], repetitions: &[PatternFinding]) -> Vec<PatternFinding> {
+        let mut findings: Vec<PatternFinding> = Vec::new();
+
+        for center in 0..emojis.len() {
+            for (mut left, mut right) in [(center as isize, center), (center as isize, center + 1)] {
+                while left >= 0 && right < emojis.len() && emojis[left as usize] == emojis[right] {
+                    left -= 1;
+                    right += 1;
+                }
+
+                let start = (left + 1) as usize;
+                let length = right - start;
+                let inside = |f: &PatternFinding| f.start <= start && start + length <= f.start + f.length;
+                if length >= 4 && !repetitions.iter().any(inside) && !findings.iter().any(inside) {
+                    findings.retain(|f| !(start <= f.start && f.start + f.length <= start + length));
+                    findings.push(PatternFinding {
+                        kind: PatternKind::Palindrome,
+                        start,
+                        length,
+                        penalty: (length / 2) as u32 * 5,
+                    });
                 }
             }
-            repetition_penalty = consecutive_same * 5;
+        }
+
+        findings
+    }
+
+    // Maximal runs of three or more neighbours that share a key.
+    fn runs<K: PartialEq>(keys: &[Option<K>], make: impl Fn(&K, usize, usize) -> PatternFinding) -> Vec<PatternFinding> {
+        let mut findings = Vec::new();
+        let mut start = 0;
+
+        for end in 1..=keys.len() {
+            if end < keys.len() && keys[end].is_some() && keys[end] == keys[start] {
+                continue;
+            }
+            if let Some(key) = &keys[start] {
+                if end - start >= 3 {
+                    findings.push(make(key, start, end - start));
+                }
+            }
+            start = end;
+        }
+
+        findings
+    }
+}
+
+impl PatternAnalyzer for AdvancedPatternAnalyzer {
+    fn analyze_patterns(&self, emojis: &[&str], classifier: &dyn EmojiClassifier) -> PatternScore {
+        let mut alternating_bonus = 0;
+        let mut sequence_bonus = 0;
+
+        let categories: Vec<Option<EmojiCategory>> = emojis.iter()
+            .map(|emoji| Some(classifier.classify(emoji)).filter(|c| *c != EmojiCategory::Unknown))
+            .collect();
+
+        if emojis.len() >= 4 {
+
+            let category_changes = categories.windows(2)
+                .filter(|window| window[0] != window[1])
+                .count() as u32;
+            alternating_bonus = (category_changes * 5).min(15);
 
             let mut unique_sequences = 0;
             for window in emojis.windows(3) {
@@ -115,19 +495,228 @@ impl PatternAnalyzer for AdvancedPatternAnalyzer {
             sequence_bonus = (unique_sequences * 3).min(10);
         }
 
+        let repetitions = Self::repetitions(emojis);
+        let mut findings = Self::palindromes(emojis, &repetitions);
+        findings.extend(Self::runs(&categories, |category, start, length| PatternFinding {
+            kind: PatternKind::CategoryRun(category.clone()),
+            start,
+            length,
+            penalty: (length - 2) as u32 * 3,
+        }));
+
+        let rows: Vec<Option<usize>> = emojis.iter()
+            .map(|emoji| classifier.picker_position(emoji).map(|position| position / PICKER_ROW_WIDTH))
+            .collect();
+        findings.extend(Self::runs(&rows, |_, start, length| PatternFinding {
+            kind: PatternKind::PickerRow,
+            start,
+            length,
+            penalty: (length - 1) as u32 * 4,
+        }));
+
+        // A run of one repeated emoji is trivially in one category and picker row; report it once.
+        findings.retain(|finding| !repetitions.iter()
+            .any(|r| r.start <= finding.start && finding.start + finding.length <= r.start + r.length));
+        findings.extend(repetitions);
+        findings.sort_by_key(|finding| (finding.start, finding.length));
+
         PatternScore {
             alternating_bonus,
-            repetition_penalty,
+            pattern_penalty: findings.iter().map(|finding| finding.penalty).sum(),
             sequence_bonus,
+            findings,
         }
     }
 }
 
+// Sequences people reach for first; an attacker tries these before anything random.
+const COMMON_SEQUENCES: &[&str] = &[
+    "😀😃😄😁", "😂🤣", "❤️🧡💛💚💙💜", "🔥🔥🔥", "👍👍", "🙈🙉🙊", "🍎🍊🍋",
+    "🐶🐱🐭", "🌞🌝", "🌑🌒🌓🌔🌕", "1️⃣2️⃣3️⃣", "😍😘", "🎉🎊", "⭐🌟✨", "🔑🔒",
+];
+
+// Among the most frequently sent emojis.
+const POPULAR_EMOJIS: &[&str] = &[
+    "😂", "❤️", "🤣", "👍", "😭", "🙏", "😘", "🥰", "😍", "😊", "🎉", "😁", "💕", "🥺",
+    "😅", "🔥", "☺️", "🤦", "🤷", "🙄", "😆", "🤗", "😉", "🎂", "🤔", "👏", "🙂", "😳",
+    "🥳", "😎", "👌", "💜", "😔", "💪", "✨", "💖", "👀", "😋", "😏", "😢", "👉", "💗",
+    "😩", "💯", "🌹", "💞", "🎈", "💙", "😃", "😀",
+];
+
+// Emojis this close together in picker order usually sit on the same row.
+const PICKER_ROW_WIDTH: usize = 8;
+
+// Printable ASCII, for clusters that are plain characters rather than emojis.
+const ASCII_ALPHABET: f64 = 95.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AttackModel {
+    name: &'static str,
+    guesses_per_second: f64,
+}
+
+const ATTACK_MODELS: [AttackModel; 4] = [
+    AttackModel { name: "Online, throttled (100/hour)", guesses_per_second: 100.0 / 3600.0 },
+    AttackModel { name: "Online, unthrottled (10/s)", guesses_per_second: 10.0 },
+    AttackModel { name: "Offline, slow hash (10k/s)", guesses_per_second: 1e4 },
+    AttackModel { name: "Offline, fast hash (10B/s)", guesses_per_second: 1e10 },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PenaltyKind {
+    CommonSequence,
+    PopularEmoji,
+    Repeat,
+    PickerNeighbour,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct EntropyPenalty {
+    kind: PenaltyKind,
+    position: usize,
+    length: usize,
+    bits_lost: f64,
+}
+
+struct StrengthEstimate {
+    entropy_bits: f64,
+    max_entropy_bits: f64,
+    penalties: Vec<EntropyPenalty>,
+    crack_times: Vec<(AttackModel, f64)>,
+}
+
+impl StrengthEstimate {
+    // Seconds to reach the password halfway through the guess space.
+    fn crack_seconds(entropy_bits: f64, model: &AttackModel) -> f64 {
+        2f64.powf(entropy_bits - 1.0).max(1.0) / model.guesses_per_second
+    }
+}
+
+fn format_duration(seconds: f64) -> String {
+    const UNITS: [(&str, f64); 5] = [
+        ("year", 365.25 * 24.0 * 3600.0),
+        ("day", 24.0 * 3600.0),
+        ("hour", 3600.0),
+        ("minute", 60.0),
+        ("second", 1.0),
+    ];
+
+    if seconds < 1.0 {
+        return "less than a second".to_string();
+    }
+    if seconds >= 100.0 * UNITS[0].1 {
+        return "centuries".to_string();
+    }
+
+    let (unit, size) = UNITS.iter().find(|(_, size)| seconds >= *size).copied().unwrap_or(UNITS[4]);
+    let count = (seconds / size).round() as u64;
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+// Scores each position by the cheapest way an attacker could have guessed it, zxcvbn-style:
+// a random pick from the whole alphabet, or one of the shortcuts below.
+#[derive(Clone)]
+struct EntropyEstimator {
+    attack_models: Vec<AttackModel>,
+}
+
+impl EntropyEstimator {
+    fn new() -> Self {
+        Self {
+            attack_models: ATTACK_MODELS.to_vec(),
+        }
+    }
+
+    fn estimate<C: EmojiClassifier>(&self, emojis: &[&str], classifier: &C) -> StrengthEstimate {
+        let keys: Vec<String> = emojis.iter().map(|emoji| emoji_key(emoji)).collect();
+        let emoji_bits = (classifier.alphabet_size().max(2) as f64).log2();
+        let random_bits = |emoji: &str| {
+            if emoji.len() == 1 && emoji.as_bytes()[0].is_ascii_graphic() {
+                ASCII_ALPHABET.log2()
+            } else {
+                emoji_bits
+            }
+        };
+
+        let mut entropy_bits = 0.0;
+        let mut max_entropy_bits = 0.0;
+        let mut penalties = Vec::new();
+        let mut i = 0;
+
+        while i < emojis.len() {
+            if let Some(length) = Self::common_sequence_at(&keys[i..]) {
+                let full: f64 = emojis[i..i + length].iter().map(|e| random_bits(e)).sum();
+                let bits = (COMMON_SEQUENCES.len() as f64).log2();
+                entropy_bits += bits;
+                max_entropy_bits += full;
+                penalties.push(EntropyPenalty { kind: PenaltyKind::CommonSequence, position: i, length, bits_lost: full - bits });
+                i += length;
+                continue;
+            }
+
+            let full = random_bits(emojis[i]);
+            let mut cheapest: Option<(PenaltyKind, f64)> = None;
+            let mut consider = |kind: PenaltyKind, bits: f64| {
+                if bits < full && cheapest.is_none_or(|(_, best)| bits < best) {
+                    cheapest = Some((kind, bits));
+                }
+            };
+
+            if keys[..i].contains(&keys[i]) {
+                consider(PenaltyKind::Repeat, (i as f64).log2() + 1.0);
+            }
+            if POPULAR_EMOJIS.iter().any(|popular| emoji_key(popular) == keys[i]) {
+                consider(PenaltyKind::PopularEmoji, (POPULAR_EMOJIS.len() as f64).log2());
+            }
+            if i > 0 {
+                let here = classifier.picker_position(emojis[i]);
+                let before = classifier.picker_position(emojis[i - 1]);
+                if let (Some(here), Some(before)) = (here, before) {
+                    if here != before && here.abs_diff(before) <= PICKER_ROW_WIDTH {
+                        consider(PenaltyKind::PickerNeighbour, (2.0 * PICKER_ROW_WIDTH as f64).log2());
+                    }
+                }
+            }
+
+            let bits = match cheapest {
+                Some((kind, bits)) => {
+                    penalties.push(EntropyPenalty { kind, position: i, length: 1, bits_lost: full - bits });
+                    bits
+                }
+                None => full,
+            };
+            entropy_bits += bits;
+            max_entropy_bits += full;
+            i += 1;
+        }
+
+        let crack_times = self.attack_models.iter()
+            .map(|model| (*model, StrengthEstimate::crack_seconds(entropy_bits, model)))
+            .collect();
+
+        StrengthEstimate {
+            entropy_bits,
+            max_entropy_bits,
+            penalties,
+            crack_times,
+        }
+    }
+
+    fn common_sequence_at(keys: &[String]) -> Option<usize> {
+        COMMON_SEQUENCES.iter()
+            .map(|sequence| grapheme::graphemes(sequence).map(emoji_key).collect::<Vec<_>>())
+            .filter(|sequence| keys.starts_with(sequence))
+            .map(|sequence| sequence.len())
+            .max()
+    }
+}
+
 struct ValidationResult {
     is_valid: bool,
     feedback: String,
     strength_score: u32,
     detailed_analysis: DetailedAnalysis,
+    failures: Vec<RuleFailure>,
 }
 
 struct DetailedAnalysis {
@@ -138,6 +727,146 @@ struct DetailedAnalysis {
     complexity_score: u32,
     categories_used: HashSet<EmojiCategory>,
     pattern_details: PatternScore,
+    strength: StrengthEstimate,
+}
+
+trait ToJson {
+    fn to_json(&self) -> String;
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// JSON has no infinity; crack times for very long passwords can overflow f64.
+fn json_number(value: f64) -> String {
+    if !value.is_finite() {
+        "null".to_string()
+    } else if value.abs() < 1e15 {
+        format!("{:.3}", value)
+    } else {
+        format!("{:e}", value)
+    }
+}
+
+fn json_array<T>(items: &[T], to_json: impl Fn(&T) -> String) -> String {
+    let items: Vec<String> = items.iter().map(to_json).collect();
+    format!("[{}]", items.join(","))
+}
+
+impl ToJson for PatternFinding {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"kind\":{},\"start\":{},\"length\":{},\"penalty\":{}}}",
+            json_string(&format!("{:?}", self.kind)), self.start, self.length, self.penalty
+        )
+    }
+}
+
+impl ToJson for PatternScore {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"alternating_bonus\":{},\"pattern_penalty\":{},\"sequence_bonus\":{},\"findings\":{}}}",
+            self.alternating_bonus,
+            self.pattern_penalty,
+            self.sequence_bonus,
+            json_array(&self.findings, ToJson::to_json)
+        )
+    }
+}
+
+impl ToJson for EntropyPenalty {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"kind\":{},\"position\":{},\"length\":{},\"bits_lost\":{}}}",
+            json_string(&format!("{:?}", self.kind)), self.position, self.length, json_number(self.bits_lost)
+        )
+    }
+}
+
+impl ToJson for StrengthEstimate {
+    fn to_json(&self) -> String {
+        let crack_times = json_array(&self.crack_times, |(model, seconds)| {
+            format!(
+                "{{\"model\":{},\"seconds\":{},\"display\":{}}}",
+                json_string(model.name), json_number(*seconds), json_string(&format_duration(*seconds))
+            )
+        });
+        format!(
+            "{{\"entropy_bits\":{},\"max_entropy_bits\":{},\"penalties\":{},\"crack_times\":{}}}",
+            json_number(self.entropy_bits),
+            json_number(self.max_entropy_bits),
+            json_array(&self.penalties, ToJson::to_json),
+            crack_times
+        )
+    }
+}
+
+impl ToJson for RuleFailure {
+    fn to_json(&self) -> String {
+        let parameter = match &self.rule {
+            PolicyRule::MinLength(min) | PolicyRule::MinUnique(min) | PolicyRule::MinEmojis(min) => {
+                format!(",\"min\":{}", min)
+            }
+            PolicyRule::MinScore(min) => format!(",\"min\":{}", min),
+            PolicyRule::MinEntropy(bits) => format!(",\"min_bits\":{}", json_number(*bits)),
+            PolicyRule::RequireClass(class) => format!(",\"class\":{}", json_string(class.name())),
+            PolicyRule::BannedSubstring(banned) => format!(",\"substring\":{}", json_string(banned)),
+            PolicyRule::NotBreached => String::new(),
+        };
+        format!(
+            "{{\"rule\":{}{},\"message\":{}}}",
+            json_string(self.rule.name()), parameter, json_string(&self.message)
+        )
+    }
+}
+
+impl ToJson for DetailedAnalysis {
+    fn to_json(&self) -> String {
+        // HashSet order is random; sort so reports are stable between runs.
+        let mut categories: Vec<String> = self.categories_used.iter().map(|c| format!("{:?}", c)).collect();
+        categories.sort();
+
+        format!(
+            "{{\"length_score\":{},\"uniqueness_score\":{},\"category_diversity_score\":{},\"pattern_score\":{},\
+             \"complexity_score\":{},\"categories\":{},\"patterns\":{},\"strength\":{}}}",
+            self.length_score,
+            self.uniqueness_score,
+            self.category_diversity_score,
+            self.pattern_score,
+            self.complexity_score,
+            json_array(&categories, |c| json_string(c)),
+            self.pattern_details.to_json(),
+            self.strength.to_json()
+        )
+    }
+}
+
+impl ToJson for ValidationResult {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"valid\":{},\"feedback\":{},\"score\":{},\"failures\":{},\"analysis\":{}}}",
+            self.is_valid,
+            json_string(&self.feedback),
+            self.strength_score,
+            json_array(&self.failures, ToJson::to_json),
+            self.detailed_analysis.to_json()
+        )
+    }
 }
 
 struct ProEmojiPasswordValidator<C, P>
@@ -147,6 +876,7 @@ where
 {
     classifier: C,
     pattern_analyzer: P,
+    estimator: EntropyEstimator,
     min_length: usize,
     min_unique: usize,
 }
@@ -160,12 +890,13 @@ where
         Self {
             classifier,
             pattern_analyzer,
+            estimator: EntropyEstimator::new(),
             min_length: 4,
             min_unique: 2,
         }
     }
 
-    fn calculate_detailed_score(&self, emojis: &[char], unique_emojis: &HashSet<char>) -> DetailedAnalysis {
+    fn calculate_detailed_score(&self, emojis: &[&str], unique_emojis: &HashSet<&str>) -> DetailedAnalysis {
 
         let length_score = if emojis.len() >= 8 {
             40  
@@ -184,17 +915,17 @@ where
         };
 
         let categories: HashSet<EmojiCategory> = emojis.iter()
-            .map(|&emoji| self.classifier.classify(emoji))
+            .map(|emoji| self.classifier.classify(emoji))
             .collect();
         let category_diversity_score = (categories.len() as u32) * 10;
 
-        let pattern_score_data = self.pattern_analyzer.analyze_patterns(emojis);
+        let pattern_score_data = self.pattern_analyzer.analyze_patterns(emojis, &self.classifier);
         let pattern_score = (pattern_score_data.alternating_bonus 
             + pattern_score_data.sequence_bonus)
-            .saturating_sub(pattern_score_data.repetition_penalty);
+            .saturating_sub(pattern_score_data.pattern_penalty);
 
         let complexity_score = emojis.iter()
-            .map(|&emoji| {
+            .map(|emoji| {
                 let category = self.classifier.classify(emoji);
                 self.classifier.get_complexity_weight(&category)
             })
@@ -209,6 +940,7 @@ where
             complexity_score,
             categories_used: categories,
             pattern_details: pattern_score_data,
+            strength: self.estimator.estimate(emojis, &self.classifier),
         }
     }
 
@@ -235,8 +967,24 @@ where
         println!("   Categories: {:?}", analysis.categories_used);
         println!("   Pattern Details:");
         println!("     - Alternating Bonus: {}", analysis.pattern_details.alternating_bonus);
-        println!("     - Repetition Penalty: {}", analysis.pattern_details.repetition_penalty);
+        println!("     - Pattern Penalty: {}", analysis.pattern_details.pattern_penalty);
         println!("     - Sequence Bonus: {}", analysis.pattern_details.sequence_bonus);
+        for finding in &analysis.pattern_details.findings {
+            println!("     - {:?} at position {} (length {}): -{}",
+                     finding.kind, finding.start + 1, finding.length, finding.penalty);
+        }
+
+        let strength = &analysis.strength;
+        println!("   Entropy: {:.1} bits (up to {:.1} bits if chosen at random)",
+                 strength.entropy_bits, strength.max_entropy_bits);
+        for penalty in &strength.penalties {
+            println!("     - {:?} at position {} (length {}): -{:.1} bits",
+                     penalty.kind, penalty.position + 1, penalty.length, penalty.bits_lost);
+        }
+        println!("   Estimated Crack Time:");
+        for (model, seconds) in &strength.crack_times {
+            println!("     - {}: {}", model.name, format_duration(*seconds));
+        }
     }
 }
 
@@ -246,20 +994,31 @@ where
     P: PatternAnalyzer,
 {
     fn validate(&self, password: &str) -> ValidationResult {
-        let emojis: Vec<char> = password.chars().collect();
-        let unique_emojis: HashSet<char> = emojis.iter().cloned().collect();
+        let emojis: Vec<&str> = grapheme::graphemes(password).collect();
+        let unique_emojis: HashSet<&str> = emojis.iter().copied().collect();
 
         let analysis = self.calculate_detailed_score(&emojis, &unique_emojis);
 
         let total_score = (analysis.length_score 
             + analysis.uniqueness_score 
             + analysis.category_diversity_score 
-            + analysis.complexity_score).min(100);
+            + analysis.complexity_score).min(100)
+            .saturating_sub(analysis.pattern_details.pattern_penalty);
 
-        let is_valid = emojis.len() >= self.min_length 
-            && unique_emojis.len() >= self.min_unique 
-            && total_score >= 40;
+        let mut failures = Vec::new();
+        if emojis.len() < self.min_length {
+            let rule = PolicyRule::MinLength(self.min_length);
+            failures.push(RuleFailure::new(&rule, format!("Use at least {} emojis.", self.min_length)));
+        }
+        if unique_emojis.len() < self.min_unique {
+            let rule = PolicyRule::MinUnique(self.min_unique);
+            failures.push(RuleFailure::new(&rule, format!("Use at least {} different emojis.", self.min_unique)));
+        }
+        if total_score < 40 {
+            failures.push(RuleFailure::new(&PolicyRule::MinScore(40), format!("Score {} is below 40.", total_score)));
+        }
 
+        let is_valid = failures.is_empty();
         let feedback = self.generate_advanced_feedback(&analysis, total_score, is_valid);
 
         ValidationResult {
@@ -267,21 +1026,568 @@ where
             feedback,
             strength_score: total_score,
             detailed_analysis: analysis,
+            failures,
+        }
+    }
+}
+
+// Embedded like the emoji data so the check works from any directory; --breached swaps in a full list.
+const BREACHED_PASSWORDS: &str = include_str!("data/breached-passwords.txt");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CharacterClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    Symbol,
+    Emoji,
+}
+
+impl CharacterClass {
+    fn description(&self) -> &'static str {
+        match self {
+            CharacterClass::Lowercase => "lowercase letter",
+            CharacterClass::Uppercase => "uppercase letter",
+            CharacterClass::Digit => "digit",
+            CharacterClass::Symbol => "symbol",
+            CharacterClass::Emoji => "emoji",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            CharacterClass::Lowercase => "lowercase",
+            CharacterClass::Uppercase => "uppercase",
+            CharacterClass::Digit => "digit",
+            CharacterClass::Symbol => "symbol",
+            CharacterClass::Emoji => "emoji",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PolicyRule {
+    /// Minimum number of visible symbols (grapheme clusters).
+    MinLength(usize),
+    MinUnique(usize),
+    RequireClass(CharacterClass),
+    MinEmojis(usize),
+    /// Case-insensitive.
+    BannedSubstring(String),
+    NotBreached,
+    MinScore(u32),
+    MinEntropy(f64),
+}
+
+impl PolicyRule {
+    // Stable identifier for reports; the Debug form changes whenever a variant does.
+    fn name(&self) -> &'static str {
+        match self {
+            PolicyRule::MinLength(_) => "min_length",
+            PolicyRule::MinUnique(_) => "min_unique",
+            PolicyRule::RequireClass(_) => "require_class",
+            PolicyRule::MinEmojis(_) => "min_emojis",
+            PolicyRule::BannedSubstring(_) => "banned_substring",
+            PolicyRule::NotBreached => "not_breached",
+            PolicyRule::MinScore(_) => "min_score",
+            PolicyRule::MinEntropy(_) => "min_entropy",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RuleFailure {
+    rule: PolicyRule,
+    message: String,
+}
+
+impl RuleFailure {
+    fn new(rule: &PolicyRule, message: String) -> Self {
+        Self { rule: rule.clone(), message }
+    }
+}
+
+struct BreachedPasswords {
+    passwords: HashSet<String>,
+}
+
+impl BreachedPasswords {
+    // One password per line; blank lines and lines starting with '#' are skipped.
+    fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_lines(&fs::read_to_string(path)?))
+    }
+
+    fn from_lines(contents: &str) -> Self {
+        let passwords = contents.lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect();
+        Self { passwords }
+    }
+
+    fn contains(&self, password: &str) -> bool {
+        self.passwords.contains(password)
+    }
+
+    fn len(&self) -> usize {
+        self.passwords.len()
+    }
+}
+
+// Policy for passwords mixing letters, digits and emojis. Scoring comes from the wrapped
+// emoji validator; validity comes only from the policy's own rules.
+struct PasswordPolicy<C, P>
+where
+    C: EmojiClassifier,
+    P: PatternAnalyzer,
+{
+    validator: ProEmojiPasswordValidator<C, P>,
+    rules: Vec<PolicyRule>,
+    breached: Option<BreachedPasswords>,
+}
+
+impl<C, P> PasswordPolicy<C, P>
+where
+    C: EmojiClassifier,
+    P: PatternAnalyzer,
+{
+    fn new(validator: ProEmojiPasswordValidator<C, P>) -> Self {
+        Self {
+            validator,
+            rules: Vec::new(),
+            breached: None,
+        }
+    }
+
+    fn standard(validator: ProEmojiPasswordValidator<C, P>) -> Self {
+        Self::new(validator)
+            .with_rule(PolicyRule::MinLength(8))
+            .with_rule(PolicyRule::RequireClass(CharacterClass::Lowercase))
+            .with_rule(PolicyRule::RequireClass(CharacterClass::Digit))
+            .with_rule(PolicyRule::MinEmojis(2))
+            .with_rule(PolicyRule::BannedSubstring("password".to_string()))
+            .with_rule(PolicyRule::MinEntropy(40.0))
+    }
+
+    fn with_rule(mut self, rule: PolicyRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    fn with_breached_list(mut self, breached: BreachedPasswords) -> Self {
+        self.breached = Some(breached);
+        if !self.rules.contains(&PolicyRule::NotBreached) {
+            self.rules.push(PolicyRule::NotBreached);
+        }
+        self
+    }
+
+    fn character_class(&self, symbol: &str) -> CharacterClass {
+        if self.validator.classifier.is_emoji_presentation(symbol) {
+            return CharacterClass::Emoji;
+        }
+        match symbol.chars().next() {
+            Some(c) if c.is_lowercase() => CharacterClass::Lowercase,
+            Some(c) if c.is_uppercase() => CharacterClass::Uppercase,
+            Some(c) if c.is_numeric() => CharacterClass::Digit,
+            _ => CharacterClass::Symbol,
+        }
+    }
+
+    fn check(&self, rule: &PolicyRule, password: &str, symbols: &[&str], classes: &[CharacterClass],
+             result: &ValidationResult) -> Option<RuleFailure> {
+        let failure = |message: String| Some(RuleFailure::new(rule, message));
+
+        match rule {
+            PolicyRule::MinLength(min) if symbols.len() < *min => {
+                failure(format!("Use at least {} characters (found {}).", min, symbols.len()))
+            }
+            PolicyRule::MinUnique(min) => {
+                let unique = symbols.iter().collect::<HashSet<_>>().len();
+                if unique < *min {
+                    failure(format!("Use at least {} different characters (found {}).", min, unique))
+                } else {
+                    None
+                }
+            }
+            PolicyRule::RequireClass(class) if !classes.contains(class) => {
+                failure(format!("Include at least one {}.", class.description()))
+            }
+            PolicyRule::MinEmojis(min) => {
+                let emojis = classes.iter().filter(|&&class| class == CharacterClass::Emoji).count();
+                if emojis < *min {
+                    failure(format!("Include at least {} emojis (found {}).", min, emojis))
+                } else {
+                    None
+                }
+            }
+            PolicyRule::BannedSubstring(banned) if password.to_lowercase().contains(&banned.to_lowercase()) => {
+                failure(format!("Must not contain \"{}\".", banned))
+            }
+            PolicyRule::NotBreached if self.breached.as_ref().is_some_and(|list| list.contains(password)) => {
+                failure("This password appears in a list of breached passwords.".to_string())
+            }
+            PolicyRule::MinScore(min) if result.strength_score < *min => {
+                failure(format!("Score {} is below the required {}.", result.strength_score, min))
+            }
+            PolicyRule::MinEntropy(min) => {
+                let bits = result.detailed_analysis.strength.entropy_bits;
+                if bits < *min {
+                    failure(format!("Estimated entropy {:.1} bits is below the required {:.1}.", bits, min))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<C, P> PasswordValidator<&str> for PasswordPolicy<C, P>
+where
+    C: EmojiClassifier,
+    P: PatternAnalyzer,
+{
+    fn validate(&self, password: &str) -> ValidationResult {
+        let mut result = self.validator.validate(password);
+        let symbols: Vec<&str> = grapheme::graphemes(password).collect();
+        let classes: Vec<CharacterClass> = symbols.iter().map(|symbol| self.character_class(symbol)).collect();
+
+        result.failures = self.rules.iter()
+            .filter_map(|rule| self.check(rule, password, &symbols, &classes, &result))
+            .collect();
+        result.is_valid = result.failures.is_empty();
+        result.feedback = if result.is_valid {
+            format!("✅ Password meets the policy. Score: {}/100", result.strength_score)
+        } else {
+            format!("❌ Password fails {} of {} rules.", result.failures.len(), self.rules.len())
+        };
+
+        result
+    }
+}
+
+// Reads the operating system's CSPRNG; std has no portable API for it, so only Unix-like
+// systems with /dev/urandom are supported.
+struct SecureRandom {
+    source: fs::File,
+}
+
+impl SecureRandom {
+    #[cfg(unix)]
+    fn new() -> io::Result<Self> {
+        Ok(Self { source: fs::File::open("/dev/urandom")? })
+    }
+
+    #[cfg(not(unix))]
+    fn new() -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "unsupported platform: --generate needs /dev/urandom"))
+    }
+
+    fn next_u64(&mut self) -> io::Result<u64> {
+        let mut bytes = [0u8; 8];
+        self.source.read_exact(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    // Uniform in 0..bound; rejection sampling avoids modulo bias.
+    fn below(&mut self, bound: usize) -> io::Result<usize> {
+        let bound = bound as u64;
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64()?;
+            if value < zone {
+                return Ok((value % bound) as usize);
+            }
         }
     }
 }
 
-fn main() {
+const MAX_GENERATED_LENGTH: usize = 24;
+const MAX_PICK_ATTEMPTS: usize = 1000;
+
+// Builds passphrases one emoji at a time, switching category on every pick and never reusing an
+// emoji, until the validator accepts the result with no pattern findings.
+struct EmojiPasswordGenerator<'a, C, P>
+where
+    C: EmojiClassifier,
+    P: PatternAnalyzer,
+{
+    validator: &'a ProEmojiPasswordValidator<C, P>,
+    categories: Vec<EmojiCategory>,
+    min_entropy_bits: f64,
+    min_score: u32,
+}
+
+impl<'a, C, P> EmojiPasswordGenerator<'a, C, P>
+where
+    C: EmojiClassifier,
+    P: PatternAnalyzer,
+{
+    fn new(validator: &'a ProEmojiPasswordValidator<C, P>) -> Self {
+        let categories = EmojiCategory::ALL.iter()
+            .filter(|category| !matches!(category, EmojiCategory::Component | EmojiCategory::Unknown))
+            .cloned()
+            .collect();
+
+        Self {
+            validator,
+            categories,
+            min_entropy_bits: 60.0,
+            min_score: 80,
+        }
+    }
+
+    fn with_min_entropy(mut self, bits: f64) -> Self {
+        self.min_entropy_bits = bits;
+        self
+    }
+
+    fn with_min_score(mut self, score: u32) -> Self {
+        self.min_score = score;
+        self
+    }
+
+    fn with_categories(mut self, categories: Vec<EmojiCategory>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    fn accepts(&self, result: &ValidationResult) -> bool {
+        result.is_valid
+            && result.strength_score >= self.min_score
+            && result.detailed_analysis.strength.entropy_bits >= self.min_entropy_bits
+    }
+
+    fn generate(&self, rng: &mut SecureRandom) -> io::Result<String> {
+        let pools: Vec<(&EmojiCategory, Vec<&str>)> = self.categories.iter()
+            .map(|category| (category, self.validator.classifier.emojis_in(category)))
+            .filter(|(_, pool)| !pool.is_empty())
+            .collect();
+        if pools.len() < 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "need emojis from at least two categories"));
+        }
+
+        let mut symbols: Vec<&str> = Vec::new();
+        let mut previous_category = None;
+
+        for _ in 0..MAX_PICK_ATTEMPTS {
+            let (category, pool) = &pools[rng.below(pools.len())?];
+            let emoji = pool[rng.below(pool.len())?];
+            if previous_category == Some(*category) || symbols.contains(&emoji) {
+                continue;
+            }
+
+            symbols.push(emoji);
+            let result = self.validator.validate(&symbols.concat());
+            if !result.detailed_analysis.pattern_details.findings.is_empty() {
+                symbols.pop();
+                continue;
+            }
+            if self.accepts(&result) {
+                return Ok(symbols.concat());
+            }
+
+            previous_category = Some(*category);
+            if symbols.len() >= MAX_GENERATED_LENGTH {
+                symbols.clear();
+                previous_category = None;
+            }
+        }
+
+        Err(io::Error::other(format!(
+            "could not reach score {} and {:.0} bits of entropy",
+            self.min_score, self.min_entropy_bits
+        )))
+    }
+}
+
+const USAGE: &str = "Usage: EmojiPasswordChecker --check [FILE|-] [--json] [--policy] [--breached FILE]
+       EmojiPasswordChecker --generate [COUNT] [--min-entropy BITS] [--min-score N] [--categories A,B,...]
+--generate reads /dev/urandom and is only supported on Unix-like systems (Linux, macOS, BSD).";
+
+// Comma-separated category names as printed by the analysis, matched case-insensitively.
+fn parse_categories(names: &str) -> Option<Vec<EmojiCategory>> {
+    names.split(',')
+        .map(|name| EmojiCategory::ALL.iter().find(|c| format!("{:?}", c).eq_ignore_ascii_case(name.trim())).cloned())
+        .collect()
+}
+
+fn run_generator(args: &[String]) -> ExitCode {
+    let mut count = 1;
+    let mut min_entropy: Option<f64> = None;
+    let mut min_score: Option<u32> = None;
+    let mut categories: Option<Vec<EmojiCategory>> = None;
+
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        let parsed = match arg {
+            "--min-entropy" => args.next().and_then(|value| value.parse().ok()).map(|bits| min_entropy = Some(bits)),
+            "--min-score" => args.next().and_then(|value| value.parse().ok()).map(|score| min_score = Some(score)),
+            "--categories" => args.next().and_then(parse_categories).map(|names| categories = Some(names)),
+            value if !value.starts_with("--") => value.parse().ok().map(|n| count = n),
+            _ => None,
+        };
+        if parsed.is_none() {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    }
+
+    let validator = ProEmojiPasswordValidator::new(AdvancedEmojiClassifier::new(), AdvancedPatternAnalyzer);
+    let mut generator = EmojiPasswordGenerator::new(&validator);
+    if let Some(bits) = min_entropy {
+        generator = generator.with_min_entropy(bits);
+    }
+    if let Some(score) = min_score {
+        generator = generator.with_min_score(score);
+    }
+    if let Some(categories) = categories {
+        generator = generator.with_categories(categories);
+    }
+
+    let generated = SecureRandom::new().and_then(|mut rng| {
+        (0..count).map(|_| generator.generate(&mut rng)).collect::<io::Result<Vec<String>>>()
+    });
+
+    match generated {
+        Ok(passwords) => {
+            for password in passwords {
+                println!("{}", password);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Cannot generate a password: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Batch mode for provisioning scripts: one candidate per line, exit code 1 if any fails.
+// Reports identify candidates by line number so passwords never end up in logs.
+fn run_checker(args: &[String]) -> ExitCode {
+    let mut input: Option<&str> = None;
+    let mut json = false;
+    let mut use_policy = false;
+    let mut breached_file: Option<&str> = None;
+
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--check" => {}
+            "--json" => json = true,
+            "--policy" => use_policy = true,
+            "--breached" => match args.next() {
+                Some(path) => breached_file = Some(path),
+                None => {
+                    eprintln!("{}", USAGE);
+                    return ExitCode::from(2);
+                }
+            },
+            path if !path.starts_with("--") && input.is_none() => input = Some(path),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let contents = match input {
+        None | Some("-") => io::read_to_string(io::stdin()),
+        Some(path) => fs::read_to_string(path),
+    };
+    let contents = match contents {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Cannot read passwords: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let classifier = AdvancedEmojiClassifier::new();
+    let validator = ProEmojiPasswordValidator::new(classifier, AdvancedPatternAnalyzer);
+    let checker: Box<dyn PasswordValidator<&str>> = if use_policy {
+        let breached = match breached_file {
+            Some(path) => match BreachedPasswords::load(path) {
+                Ok(breached) => breached,
+                Err(e) => {
+                    eprintln!("Cannot read breached passwords ({}: {})", path, e);
+                    return ExitCode::from(2);
+                }
+            },
+            None => BreachedPasswords::from_lines(BREACHED_PASSWORDS),
+        };
+        Box::new(PasswordPolicy::standard(validator).with_breached_list(breached))
+    } else {
+        Box::new(validator)
+    };
+
+    let mut reports = Vec::new();
+    let mut checked = 0;
+    let mut failed = 0;
+    for (index, line) in contents.lines().enumerate() {
+        let password = line.trim_end_matches('\r');
+        if password.is_empty() {
+            continue;
+        }
+
+        let result = checker.validate(password);
+        checked += 1;
+        if !result.is_valid {
+            failed += 1;
+        }
+
+        if json {
+            reports.push(format!("{{\"line\":{},\"result\":{}}}", index + 1, result.to_json()));
+        } else {
+            let status = if result.is_valid { "✅ pass" } else { "❌ fail" };
+            println!("line {}: {} (score {}, {:.1} bits)",
+                     index + 1, status, result.strength_score, result.detailed_analysis.strength.entropy_bits);
+            for failure in &result.failures {
+                println!("    {}", failure.message);
+            }
+        }
+    }
+
+    if json {
+        println!(
+            "{{\"checked\":{},\"passed\":{},\"failed\":{},\"results\":[{}]}}",
+            checked, checked - failed, failed, reports.join(",")
+        );
+    } else {
+        println!("Checked {} passwords: {} passed, {} failed", checked, checked - failed, failed);
+    }
+
+    if failed > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "--generate") {
+        return run_generator(&args[1..]);
+    }
+    if !args.is_empty() {
+        return run_checker(&args);
+    }
+
     println!("🎮 EMOJI PASSWORD CHECKER - PRO LEVEL ⚡");
     println!("{}", "=".repeat(50));
     println!("Learning: Traits, generics, advanced patterns, extensible architecture");
     println!();
 
-    let classifier = AdvancedEmojiClassifier::new();
+    let classifier = AdvancedEmojiClassifier::new()
+        .with_weight(EmojiCategory::Flag, 5);
     let pattern_analyzer = AdvancedPatternAnalyzer;
     let validator = ProEmojiPasswordValidator::new(classifier.clone(), pattern_analyzer.clone()); 
+    let breached = BreachedPasswords::from_lines(BREACHED_PASSWORDS);
+    println!("Loaded {} breached passwords", breached.len());
+    let policy = PasswordPolicy::standard(ProEmojiPasswordValidator::new(classifier.clone(), pattern_analyzer.clone()))
+        .with_breached_list(breached);
+    println!();
 
-    let test_passwords = vec![
+    let test_passwords = [
         "😀😎🤔🔑",              
         "🔥🔥🔥",                
         "😀😎🤔🔑🌟🌈⚡🎯",      
@@ -289,21 +1595,60 @@ fn main() {
         "😀😀😀😀😀😀",          
         "🦄🍎🔑😊🌟",            
         "😀🔑😀🔑😀🔑",          
+        "👨‍👩‍👧🇯🇵👍🏽🛡️",
     ];
 
     println!("Testing emoji passwords with advanced analysis:");
     for (i, password) in test_passwords.iter().enumerate() {
         let result = validator.validate(password);
-        println!("{}. Password: {} | Valid: {} | Score: {}", 
-                 i + 1, password, result.is_valid, result.strength_score);
+        println!("{}. Password: {} | Valid: {} | Score: {} | Entropy: {:.1} bits", 
+                 i + 1, password, result.is_valid, result.strength_score,
+                 result.detailed_analysis.strength.entropy_bits);
         println!("   {}", result.feedback);
         validator.print_advanced_analysis(&result.detailed_analysis);
         println!();
     }
 
+    println!("📜 Mixed text + emoji policy:");
+    for password in ["hunter2", "password1🔥🦄", "ilove🍕🍕2", "sunset7🌊🦊🎯", "K7x🦉q🪐m9🧩"] {
+        let result = policy.validate(password);
+        println!("   {} | Valid: {} | {}", password, result.is_valid, result.feedback);
+        for failure in &result.failures {
+            println!("     - {:?}: {}", failure.rule, failure.message);
+        }
+    }
+    println!();
+
+    println!("🎲 Generated passwords:");
+    let generator = EmojiPasswordGenerator::new(&validator);
+    match SecureRandom::new() {
+        Ok(mut rng) => {
+            for _ in 0..3 {
+                match generator.generate(&mut rng) {
+                    Ok(password) => {
+                        let result = validator.validate(&password);
+                        println!("   {} | Score: {} | Entropy: {:.1} bits",
+                                 password, result.strength_score, result.detailed_analysis.strength.entropy_bits);
+                    }
+                    Err(e) => println!("   Generation failed: {}", e),
+                }
+            }
+        }
+        Err(e) => println!("   No secure random source available: {}", e),
+    }
+    println!();
+
+    println!("📚 Emoji database: {} emojis", classifier.emoji_count());
+    for emoji in ["👨‍👩‍👧", "🇯🇵", "👍🏽", "🛡"] {
+        if let Some(info) = classifier.info(emoji) {
+            let subgroup = if info.subgroup.is_empty() { String::new() } else { format!(" / {}", info.subgroup) };
+            println!("   {} {} ({}{})", emoji, info.name, info.group, subgroup);
+        }
+    }
+    println!();
+
     println!("🎯 Emoji Complexity Weights (for advanced scoring):");
-    for category in [EmojiCategory::Face, EmojiCategory::Food, EmojiCategory::Animal, 
-                     EmojiCategory::Nature, EmojiCategory::Object] {
+    for category in EmojiCategory::ALL {
         let weight = classifier.get_complexity_weight(&category);
         println!("   {:?}: {} points", category, weight);
     }
@@ -317,8 +1662,360 @@ fn main() {
     println!();
 
     println!("🚀 Advanced Features:");
-    println!("   • Pattern detection (alternating, sequences)");
+    println!("   • Pattern detection (repeats of any period, mirrors, category and picker-row runs)");
+    println!("   • Entropy and crack-time estimates per attack model");
     println!("   • Complexity weighting by emoji category");
     println!("   • Detailed scoring breakdown");
     println!("   • Trait-based extensible design");
+    println!("   • JSON reports and a --check mode for scripts");
+    println!("   • CSPRNG-backed emoji password generator (--generate)");
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator() -> ProEmojiPasswordValidator<AdvancedEmojiClassifier, AdvancedPatternAnalyzer> {
+        ProEmojiPasswordValidator::new(AdvancedEmojiClassifier::new(), AdvancedPatternAnalyzer)
+    }
+
+    fn clusters(text: &str) -> Vec<&str> {
+        grapheme::graphemes(text).collect()
+    }
+
+    #[test]
+    fn test_emoji_sequences_are_single_graphemes() {
+        assert_eq!(clusters("👨‍👩‍👧🇯🇵👍🏽🛡️"), ["👨‍👩‍👧", "🇯🇵", "👍🏽", "🛡️"]);
+        assert_eq!(clusters("1️⃣#️⃣"), ["1️⃣", "#️⃣"]);
+        assert_eq!(clusters("🏳️‍🌈🏴󠁧󠁢󠁳󠁣󠁴󠁿"), ["🏳️‍🌈", "🏴󠁧󠁢󠁳󠁣󠁴󠁿"]);
+        assert_eq!(clusters("🧑🏽‍🤝‍🧑🏿"), ["🧑🏽‍🤝‍🧑🏿"]);
+    }
+
+    #[test]
+    fn test_regional_indicators_pair_up() {
+        assert_eq!(clusters("🇯🇵🇫🇷🇩"), ["🇯🇵", "🇫🇷", "🇩"]);
+    }
+
+    #[test]
+    fn test_text_graphemes() {
+        assert_eq!(clusters("ab\r\nc"), ["a", "b", "\r\n", "c"]);
+        assert_eq!(clusters("\n\r"), ["\n", "\r"]);
+        assert_eq!(clusters("e\u{301}x"), ["e\u{301}", "x"]);
+        assert_eq!(clusters("한국"), ["한", "국"]);
+        assert_eq!(clusters("\u{1100}\u{1161}\u{11A8}"), ["\u{1100}\u{1161}\u{11A8}"]);
+        // A ZWJ only glues pictographs together; after a letter the next emoji starts afresh.
+        assert_eq!(clusters("a\u{200D}😀"), ["a\u{200D}", "😀"]);
+        assert!(clusters("").is_empty());
+    }
+
+    #[test]
+    fn test_emoji_test_parser() {
+        let classifier = AdvancedEmojiClassifier::from_emoji_test("\
+# group: Smileys & Emotion
+# subgroup: face-smiling
+1F600 ; fully-qualified # 😀 E1.0 grinning face
+263A FE0F ; fully-qualified # ☺️ E0.6 smiling face
+263A ; unqualified # ☺ E0.6 smiling face
+# group: Component
+# subgroup: skin-tone
+1F3FD ; component # 🏽 E1.0 medium skin tone
+not an entry ; fully-qualified # ? E0.0 bogus
+");
+
+        assert_eq!(classifier.emoji_count(), 3);
+        let info = classifier.info("☺").unwrap();
+        assert_eq!((info.emoji.as_str(), info.name.as_str(), info.position), ("☺️", "smiling face", 1));
+        assert_eq!((info.group.as_str(), info.subgroup.as_str()), ("Smileys & Emotion", "face-smiling"));
+        assert_eq!(classifier.classify("🏽"), EmojiCategory::Component);
+        assert_eq!(classifier.info("🏽").unwrap().subgroup, "skin-tone");
+        assert_eq!(classifier.emojis_in(&EmojiCategory::Face), ["😀", "☺️"]);
+    }
+
+    #[test]
+    fn test_classifier_uses_unicode_groups() {
+        let classifier = AdvancedEmojiClassifier::new();
+
+        assert_eq!(classifier.classify("😀"), EmojiCategory::Face);
+        assert_eq!(classifier.classify("👍🏽"), EmojiCategory::People);
+        assert_eq!(classifier.classify("🦰"), EmojiCategory::Component);
+        assert_eq!(classifier.classify("🦊"), EmojiCategory::Nature);
+        assert_eq!(classifier.classify("🍕"), EmojiCategory::Food);
+        assert_eq!(classifier.classify("🚀"), EmojiCategory::Travel);
+        assert_eq!(classifier.classify("⚽"), EmojiCategory::Activity);
+        assert_eq!(classifier.classify("🔑"), EmojiCategory::Object);
+        assert_eq!(classifier.classify("1️⃣"), EmojiCategory::Symbol);
+        assert_eq!(classifier.classify("🇯🇵"), EmojiCategory::Flag);
+        assert_eq!(classifier.classify("a"), EmojiCategory::Unknown);
+
+        // Missing presentation selectors and unlisted ZWJ combinations still resolve.
+        assert_eq!(classifier.classify("🛡"), EmojiCategory::Object);
+        assert_eq!(classifier.classify("🦊‍🍕"), EmojiCategory::Nature);
+
+        let info = classifier.info("👨‍👩‍👧").unwrap();
+        assert_eq!((info.name.as_str(), info.subgroup.as_str()), ("family: man, woman, girl", "family"));
+        assert_eq!(classifier.info("😀").unwrap().subgroup, "face-smiling");
+        assert!(classifier.picker_position("😀") < classifier.picker_position("😃"));
+    }
+
+    fn estimate(password: &str) -> StrengthEstimate {
+        let symbols: Vec<&str> = grapheme::graphemes(password).collect();
+        EntropyEstimator::new().estimate(&symbols, &AdvancedEmojiClassifier::new())
+    }
+
+    #[test]
+    fn test_entropy_of_unrelated_emojis() {
+        let classifier = AdvancedEmojiClassifier::new();
+        let per_emoji = (classifier.alphabet_size() as f64).log2();
+
+        let strength = estimate("🦉🚂🧀🎻");
+        assert!(strength.penalties.is_empty());
+        assert!((strength.entropy_bits - 4.0 * per_emoji).abs() < 1e-9);
+        assert_eq!(strength.entropy_bits, strength.max_entropy_bits);
+
+        let ascii = estimate("a🦉");
+        assert!((ascii.entropy_bits - (95f64.log2() + per_emoji)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_entropy_penalties() {
+        let kinds = |password: &str| -> Vec<(PenaltyKind, usize, usize)> {
+            estimate(password).penalties.iter().map(|p| (p.kind, p.position, p.length)).collect()
+        };
+
+        assert_eq!(kinds("🦉🙈🙉🙊"), [(PenaltyKind::CommonSequence, 1, 3)]);
+        assert_eq!(kinds("🦉🚂🦉"), [(PenaltyKind::Repeat, 2, 1)]);
+        assert_eq!(kinds("🦉😂"), [(PenaltyKind::PopularEmoji, 1, 1)]);
+        assert_eq!(kinds("🦉🚂🧀🍖"), [(PenaltyKind::PickerNeighbour, 3, 1)]);
+
+        let weak = estimate("❤️🧡💛💚💙💜");
+        assert!(weak.entropy_bits < 5.0);
+        assert!(weak.max_entropy_bits > 60.0);
+    }
+
+    #[test]
+    fn test_crack_times() {
+        let fast = ATTACK_MODELS[3];
+        assert_eq!(StrengthEstimate::crack_seconds(0.0, &fast), 1e-10);
+        assert_eq!(StrengthEstimate::crack_seconds(41.0, &fast), 2f64.powi(40) / 1e10);
+
+        let strength = estimate("🦉🚂🧀🎻");
+        assert_eq!(strength.crack_times.len(), ATTACK_MODELS.len());
+        assert!(strength.crack_times.windows(2).all(|pair| pair[0].1 > pair[1].1));
+
+        assert_eq!(format_duration(0.5), "less than a second");
+        assert_eq!(format_duration(1.0), "1 second");
+        assert_eq!(format_duration(90.0), "2 minutes");
+        assert_eq!(format_duration(3.0 * 86_400.0), "3 days");
+        assert_eq!(format_duration(1e12), "centuries");
+    }
+
+    fn policy() -> PasswordPolicy<AdvancedEmojiClassifier, AdvancedPatternAnalyzer> {
+        PasswordPolicy::standard(validator()).with_breached_list(BreachedPasswords::from_lines(BREACHED_PASSWORDS))
+    }
+
+    fn failed_rules(result: &ValidationResult) -> Vec<PolicyRule> {
+        result.failures.iter().map(|failure| failure.rule.clone()).collect()
+    }
+
+    #[test]
+    fn test_policy_accepts_mixed_password() {
+        let result = policy().validate("K7x🦉q🪐m9🧩");
+        assert!(result.is_valid, "{:?}", result.failures);
+        assert!(result.feedback.starts_with("✅"));
+    }
+
+    #[test]
+    fn test_policy_reports_each_failed_rule() {
+        let result = policy().validate("password1🔥🦄");
+        assert!(!result.is_valid);
+        assert_eq!(failed_rules(&result), [PolicyRule::BannedSubstring("password".to_string())]);
+        assert_eq!(result.feedback, "❌ Password fails 1 of 7 rules.");
+
+        let result = policy().validate("HUNTER");
+        assert_eq!(failed_rules(&result), [
+            PolicyRule::MinLength(8),
+            PolicyRule::RequireClass(CharacterClass::Lowercase),
+            PolicyRule::RequireClass(CharacterClass::Digit),
+            PolicyRule::MinEmojis(2),
+            PolicyRule::MinEntropy(40.0),
+        ]);
+        assert_eq!(result.failures[3].message, "Include at least 2 emojis (found 0).");
+    }
+
+    #[test]
+    fn test_text_presentation_symbols_are_not_emojis() {
+        let policy = policy();
+        assert_eq!(policy.character_class("©"), CharacterClass::Symbol);
+        assert_eq!(policy.character_class("™\u{FE0E}"), CharacterClass::Symbol);
+        assert_eq!(policy.character_class("1\u{20E3}"), CharacterClass::Digit);
+        assert_eq!(policy.character_class("©️"), CharacterClass::Emoji);
+        assert_eq!(policy.character_class("😀"), CharacterClass::Emoji);
+        assert_eq!(policy.character_class("👁️‍🗨"), CharacterClass::Emoji);
+        assert_eq!(policy.character_class("É"), CharacterClass::Uppercase);
+
+        let result = policy.validate("abc©®™12345");
+        assert!(failed_rules(&result).contains(&PolicyRule::MinEmojis(2)));
+        assert!(!failed_rules(&policy.validate("abc©️®️™️12345")).contains(&PolicyRule::MinEmojis(2)));
+    }
+
+    #[test]
+    fn test_breached_passwords() {
+        let breached = BreachedPasswords::from_lines("# comment\r\nletmein\r\n\nqwerty🔥\n");
+        assert_eq!(breached.len(), 2);
+        assert!(breached.contains("letmein"));
+        assert!(breached.contains("qwerty🔥"));
+        assert!(!breached.contains("# comment"));
+
+        let policy = PasswordPolicy::new(validator()).with_breached_list(breached);
+        assert_eq!(failed_rules(&policy.validate("letmein")), [PolicyRule::NotBreached]);
+        assert!(policy.validate("letmein2").is_valid);
+        assert!(BreachedPasswords::from_lines(BREACHED_PASSWORDS).contains("123456"));
+    }
+
+    #[test]
+    fn test_rule_failures_to_json() {
+        let json = |rule: PolicyRule| RuleFailure::new(&rule, "Nope.".to_string()).to_json();
+
+        assert_eq!(json(PolicyRule::MinLength(8)), r#"{"rule":"min_length","min":8,"message":"Nope."}"#);
+        assert_eq!(json(PolicyRule::RequireClass(CharacterClass::Digit)),
+                   r#"{"rule":"require_class","class":"digit","message":"Nope."}"#);
+        assert_eq!(json(PolicyRule::BannedSubstring("pa\"ss".to_string())),
+                   r#"{"rule":"banned_substring","substring":"pa\"ss","message":"Nope."}"#);
+        assert_eq!(json(PolicyRule::MinEntropy(40.0)), r#"{"rule":"min_entropy","min_bits":40.000,"message":"Nope."}"#);
+        assert_eq!(json(PolicyRule::NotBreached), r#"{"rule":"not_breached","message":"Nope."}"#);
+    }
+
+    #[test]
+    fn test_validation_result_to_json() {
+        assert_eq!(json_string("a\"b\\c\n\u{1}😀"), r#""a\"b\\c\n\u0001😀""#);
+        assert_eq!(json_number(f64::INFINITY), "null");
+        assert_eq!(json_number(1.5), "1.500");
+        assert_eq!(json_number(2e20), "2e20");
+
+        let json = validator().validate("🔥🔥🔥").to_json();
+        assert!(json.starts_with(r#"{"valid":false,"feedback":"❌ Password too short! Use at least 4 emojis.","score":"#));
+        assert!(json.contains(r#"{"rule":"min_length","min":4,"message":"Use at least 4 emojis."}"#));
+        assert!(json.contains(r#""categories":["Travel"]"#));
+        assert!(json.contains(r#""crack_times":[{"model":"Online, throttled (100/hour)","seconds":"#));
+    }
+
+    fn check(args: &[&str], passwords: &str) -> ExitCode {
+        static FILES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let file = FILES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("emoji-check-{}-{}.txt", std::process::id(), file));
+        fs::write(&path, passwords).unwrap();
+        let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        args.push(path.to_string_lossy().into_owned());
+
+        let code = run_checker(&args);
+        fs::remove_file(&path).unwrap();
+        code
+    }
+
+    #[test]
+    fn test_checker_exit_codes() {
+        assert_eq!(check(&["--check"], "🦉🚂🧀🎻🌋🪐🧩🦊\n\n"), ExitCode::SUCCESS);
+        assert_eq!(check(&["--check", "--json"], "🦉🚂🧀🎻🌋🪐🧩🦊\n🔥🔥🔥\n"), ExitCode::FAILURE);
+        assert_eq!(check(&["--check", "--policy"], "123456\r\n"), ExitCode::FAILURE);
+        assert_eq!(check(&["--check", "--bogus"], ""), ExitCode::from(2));
+        assert_eq!(run_checker(&["--check".to_string(), "/no/such/file".to_string()]), ExitCode::from(2));
+    }
+
+    fn findings(password: &str) -> Vec<(PatternKind, usize, usize, u32)> {
+        let symbols: Vec<&str> = grapheme::graphemes(password).collect();
+        AdvancedPatternAnalyzer.analyze_patterns(&symbols, &AdvancedEmojiClassifier::new())
+            .findings
+            .into_iter()
+            .map(|f| (f.kind, f.start, f.length, f.penalty))
+            .collect()
+    }
+
+    #[test]
+    fn test_repetition_findings() {
+        assert_eq!(findings("😀😀😀😀"), [(PatternKind::Repetition { period: 1 }, 0, 4, 15)]);
+        assert_eq!(findings("🦉🚂🦉🚂🦉🚂"), [(PatternKind::Repetition { period: 2 }, 0, 6, 20)]);
+        assert_eq!(findings("🎻🦉🚂🧀🦉🚂🧀"), [(PatternKind::Repetition { period: 3 }, 1, 6, 15)]);
+        assert!(findings("🦉🚂🧀🎻").is_empty());
+    }
+
+    #[test]
+    fn test_palindrome_findings() {
+        assert_eq!(findings("🦉🚂🧀🚂🦉"), [(PatternKind::Palindrome, 0, 5, 10)]);
+        assert_eq!(findings("🎻🦉🚂🚂🦉"), [
+            (PatternKind::Palindrome, 1, 4, 10),
+            (PatternKind::Repetition { period: 1 }, 2, 2, 5),
+        ]);
+    }
+
+    #[test]
+    fn test_run_findings() {
+        assert_eq!(findings("🚂🦊🐸🌵🦉"), [(PatternKind::CategoryRun(EmojiCategory::Nature), 1, 4, 6)]);
+        assert_eq!(findings("🎻😀😃😄"), [
+            (PatternKind::CategoryRun(EmojiCategory::Face), 1, 3, 3),
+            (PatternKind::PickerRow, 1, 3, 8),
+        ]);
+    }
+
+    #[test]
+    fn test_pattern_penalties_lower_the_score() {
+        let validator = validator();
+        let varied = validator.validate("🦉🚂🧀🎻🌋🪐🧩🦊");
+        let repeated = validator.validate("🦉🚂🦉🚂🦉🚂🦉🚂");
+
+        assert_eq!(repeated.detailed_analysis.pattern_details.pattern_penalty, 30);
+        assert!(repeated.strength_score < varied.strength_score);
+    }
+
+    #[test]
+    fn test_secure_random_stays_in_range() {
+        let mut rng = SecureRandom::new().unwrap();
+        let mut seen = [false; 5];
+        for _ in 0..500 {
+            seen[rng.below(5).unwrap()] = true;
+        }
+        assert!(seen.iter().all(|&hit| hit));
+    }
+
+    #[test]
+    fn test_generated_passwords_round_trip_through_validator() {
+        let validator = validator();
+        let generator = EmojiPasswordGenerator::new(&validator);
+        let mut rng = SecureRandom::new().unwrap();
+
+        for _ in 0..20 {
+            let password = generator.generate(&mut rng).unwrap();
+            let result = validator.validate(&password);
+
+            assert!(result.is_valid, "{} was rejected: {}", password, result.feedback);
+            assert!(result.strength_score >= 80);
+            assert!(result.detailed_analysis.strength.entropy_bits >= 60.0);
+            assert!(result.detailed_analysis.pattern_details.findings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_generator_meets_higher_targets() {
+        let validator = validator();
+        let generator = EmojiPasswordGenerator::new(&validator).with_min_entropy(100.0).with_min_score(90);
+        let mut rng = SecureRandom::new().unwrap();
+
+        let password = generator.generate(&mut rng).unwrap();
+        let symbols = grapheme::graphemes(&password).count();
+        let result = validator.validate(&password);
+
+        assert!(symbols >= 8);
+        assert!(result.strength_score >= 90);
+        assert!(result.detailed_analysis.strength.entropy_bits >= 100.0);
+    }
+
+    #[test]
+    fn test_generator_needs_two_categories() {
+        let validator = validator();
+        let generator = EmojiPasswordGenerator::new(&validator).with_categories(vec![EmojiCategory::Food]);
+        let mut rng = SecureRandom::new().unwrap();
+
+        assert!(generator.generate(&mut rng).is_err());
+    }
 }
@@ -1,33 +1,121 @@
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
 use std::io::{self, Write};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 struct WordCategory {
     name: &'static str,
     words: &'static [&'static str],
     description: &'static str,
+    adversarial: bool,
+    rules: RuleSet,
 }
 
 impl WordCategory {
-    const fn new(name: &'static str, words: &'static [&'static str], description: &'static str) -> Self {
-        Self { name, words, description }
+    const fn new(name: &'static str, words: &'static [&'static str], description: &'static str, rules: RuleSet) -> Self {
+        Self { name, words, description, adversarial: false, rules }
+    }
+
+    const fn adversarial(name: &'static str, words: &'static [&'static str], description: &'static str, rules: RuleSet) -> Self {
+        Self { name, words, description, adversarial: true, rules }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuleSet {
+    pub max_wrong_guesses: usize,
+    pub max_hints: usize,
+    pub turn_time_limit: Option<Duration>,
+    pub allow_word_guesses: bool,
+    /// Wrong guesses charged for an incorrect whole-word guess.
+    pub wrong_word_penalty: usize,
+    pub score_multiplier: u32,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        config::STANDARD_RULES
+    }
+}
+
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Wrong guesses allowed: {}", self.max_wrong_guesses)?;
+        writeln!(f, "Available hints: {}", self.max_hints)?;
+        match self.turn_time_limit {
+            Some(limit) => writeln!(f, "Turn time limit: {}s", limit.as_secs())?,
+            None => writeln!(f, "Turn time limit: none")?,
+        }
+        if self.allow_word_guesses {
+            writeln!(f, "Whole-word guesses: allowed (a wrong one costs {})", self.wrong_word_penalty)?;
+        } else {
+            writeln!(f, "Whole-word guesses: not allowed")?;
+        }
+        write!(f, "Score multiplier: x{}", self.score_multiplier)
     }
 }
 
 mod config {
-    use super::WordCategory;
+    use super::{RuleSet, WordCategory};
+    use std::time::Duration;
 
     pub const EASY_WORDS: &[&str] = &["CAT", "DOG", "SUN", "CAR", "BOOK", "TREE", "FISH", "BIRD", "HOME", "LOVE"];
     pub const MEDIUM_WORDS: &[&str] = &["COMPUTER", "ELEPHANT", "MOUNTAIN", "RAINBOW", "BICYCLE", "KITCHEN", "LIBRARY", "GARDEN", "PICTURE", "FREEDOM"];
 
-    pub const CATEGORIES: [WordCategory; 2] = [
-        WordCategory::new("Easy", EASY_WORDS, "3-4 letter words"),
-        WordCategory::new("Medium", MEDIUM_WORDS, "6-8 letter words"),
+    pub const EVIL_WORDS: &[&str] = &[
+        "BAKE", "CAKE", "LAKE", "MAKE", "RAKE", "TAKE", "WAKE", "BARE", "CARE", "DARE", "HARE", "MARE",
+        "RARE", "WARE", "DATE", "FATE", "GATE", "HATE", "LATE", "MATE", "RATE", "BIKE", "HIKE", "LIKE",
+        "PIKE", "BONE", "CONE", "DONE", "GONE", "LONE", "TONE", "ZONE", "BELL", "CELL", "DULL", "FILL",
     ];
 
     pub const MAX_WRONG_GUESSES: usize = 6;
     pub const MAX_HINTS: usize = 2;
+
+    pub const STANDARD_RULES: RuleSet = RuleSet {
+        max_wrong_guesses: MAX_WRONG_GUESSES,
+        max_hints: MAX_HINTS,
+        turn_time_limit: None,
+        allow_word_guesses: true,
+        wrong_word_penalty: 2,
+        score_multiplier: 1,
+    };
+
+    pub const EASY_RULES: RuleSet = RuleSet {
+        max_wrong_guesses: 8,
+        max_hints: 3,
+        wrong_word_penalty: 1,
+        ..STANDARD_RULES
+    };
+
+    pub const MEDIUM_RULES: RuleSet = RuleSet {
+        turn_time_limit: Some(Duration::from_secs(30)),
+        score_multiplier: 2,
+        ..STANDARD_RULES
+    };
+
+    pub const EVIL_RULES: RuleSet = RuleSet {
+        max_hints: 1,
+        turn_time_limit: Some(Duration::from_secs(20)),
+        wrong_word_penalty: 3,
+        score_multiplier: 3,
+        ..STANDARD_RULES
+    };
+
+    pub const CATEGORIES: [WordCategory; 3] = [
+        WordCategory::new("Easy", EASY_WORDS, "3-4 letter words", EASY_RULES),
+        WordCategory::new("Medium", MEDIUM_WORDS, "6-8 letter words", MEDIUM_RULES),
+        WordCategory::adversarial("Evil", EVIL_WORDS, "the word dodges your guesses", EVIL_RULES),
+    ];
+
+    pub const PROFILES_FILE: &str = "hangman_profiles.txt";
+
+    // Hosting stays on this machine unless `--public` (or an explicit `--host ADDR`) opts in:
+    // the protocol has no authentication.
+    pub const HOST_ADDR: &str = "127.0.0.1:7878";
+    pub const PUBLIC_HOST_ADDR: &str = "0.0.0.0:7878";
+    pub const JOIN_ADDR: &str = "127.0.0.1:7878";
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -67,31 +155,87 @@ impl fmt::Display for GameStats {
     }
 }
 
-#[derive(Debug)]
-struct SimpleRng {
-    seed: u64,
-}
+mod rng {
+    use std::collections::hash_map::RandomState;
+    use std::fmt;
+    use std::hash::BuildHasher;
+    use std::time::{SystemTime, UNIX_EPOCH};
 
-impl SimpleRng {
-    fn new() -> Self {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        use std::time::{SystemTime, UNIX_EPOCH};
+    const SECONDS_PER_DAY: u64 = 86_400;
 
-        let mut hasher = DefaultHasher::new();
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
+    pub trait RandomSource: fmt::Debug {
+        fn next_u64(&mut self) -> u64;
 
-        Self { seed: hasher.finish() }
+        /// Uniform value in `0..max`, rejecting the biased tail instead of taking a plain modulo.
+        fn gen_range(&mut self, max: usize) -> usize {
+            assert!(max > 0, "gen_range called with an empty range");
+
+            let max = max as u64;
+            let zone = u64::MAX - u64::MAX % max;
+            loop {
+                let value = self.next_u64();
+                if value < zone {
+                    return (value % max) as usize;
+                }
+            }
+        }
     }
 
-    fn gen_range(&mut self, max: usize) -> usize {
+    #[derive(Debug, Clone)]
+    pub struct SplitMix64 {
+        state: u64,
+    }
+
+    impl SplitMix64 {
+        pub fn from_seed(seed: u64) -> Self {
+            Self { state: seed }
+        }
 
-        self.seed = self.seed.wrapping_mul(1103515245).wrapping_add(12345);
-        (self.seed as usize) % max
+        pub fn from_entropy() -> Self {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default();
+
+            Self::from_seed(RandomState::new().hash_one(nanos))
+        }
+
+        pub fn fork(&mut self) -> Self {
+            Self::from_seed(self.next_u64())
+        }
+    }
+
+    impl RandomSource for SplitMix64 {
+        fn next_u64(&mut self) -> u64 {
+            self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+    }
+
+    /// Days since the Unix epoch (UTC), shared by every player on the same date.
+    pub fn today() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() / SECONDS_PER_DAY)
+            .unwrap_or_default()
+    }
+
+    /// Converts a day number from [`today`] into a `YYYY-MM-DD` string.
+    pub fn format_day(day: u64) -> String {
+        let z = day as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let d = doy - (153 * mp + 2) / 5 + 1;
+        let m = if mp < 10 { mp + 3 } else { mp - 9 };
+        let y = yoe + era * 400 + i64::from(m <= 2);
+
+        format!("{:04}-{:02}-{:02}", y, m, d)
     }
 }
 
@@ -100,11 +244,12 @@ pub struct HangmanGame {
     word: String,
     guessed_letters: HashSet<char>,
     wrong_guesses: usize,
-    max_wrong: usize,
     category: String,
     hints_used: usize,
-    max_hints: usize,
-    rng: SimpleRng,
+    rules: RuleSet,
+    rng: Box<dyn rng::RandomSource>,
+    // Words still consistent with every answer so far; `None` when the word was fixed up front.
+    family: Option<Vec<String>>,
 }
 
 impl HangmanGame {
@@ -114,12 +259,75 @@ impl HangmanGame {
             word: word.to_uppercase(),
             guessed_letters: HashSet::new(),
             wrong_guesses: 0,
-            max_wrong: config::MAX_WRONG_GUESSES,
             category: category.to_string(),
             hints_used: 0,
-            max_hints: config::MAX_HINTS,
-            rng: SimpleRng::new(),
+            rules: RuleSet::default(),
+            rng: Box::new(rng::SplitMix64::from_entropy()),
+            family: None,
+        }
+    }
+
+    pub fn new_adversarial(words: &[&str], category: &str) -> Self {
+        let mut by_length: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for word in words {
+            let word = word.to_uppercase();
+            by_length.entry(word.chars().count()).or_default().push(word);
+        }
+
+        let family = by_length
+            .into_values()
+            .max_by_key(Vec::len)
+            .unwrap_or_default();
+
+        let mut game = Self::new(family.first().map_or("", String::as_str), category);
+        game.family = Some(family);
+        game
+    }
+
+    pub fn with_rng(mut self, rng: impl rng::RandomSource + 'static) -> Self {
+        self.rng = Box::new(rng);
+        self
+    }
+
+    pub fn with_rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    pub fn is_adversarial(&self) -> bool {
+        self.family.is_some()
+    }
+
+    fn dodge(&mut self, letter: char) {
+        let Some(family) = self.family.take() else {
+            return;
+        };
+
+        let mut partitions: BTreeMap<Vec<usize>, Vec<String>> = BTreeMap::new();
+        for word in family {
+            let positions = word
+                .chars()
+                .enumerate()
+                .filter(|&(_, c)| c == letter)
+                .map(|(i, _)| i)
+                .collect();
+            partitions.entry(positions).or_default().push(word);
+        }
+
+        // Keep the largest family, preferring the one that reveals the fewest letters.
+        let (_, largest) = partitions
+            .into_iter()
+            .max_by_key(|(positions, words)| (words.len(), Reverse(positions.len())))
+            .unwrap_or_default();
+
+        if let Some(word) = largest.first() {
+            self.word = word.clone();
         }
+        self.family = Some(largest);
     }
 
     pub fn display_word(&self) -> String {
@@ -140,7 +348,7 @@ impl HangmanGame {
     }
 
     pub fn is_lost(&self) -> bool {
-        self.wrong_guesses >= self.max_wrong
+        self.wrong_guesses >= self.rules.max_wrong_guesses
     }
 
     pub fn make_guess(&mut self, letter: char) -> GuessResult {
@@ -155,6 +363,7 @@ impl HangmanGame {
         }
 
         self.guessed_letters.insert(letter);
+        self.dodge(letter);
 
         if self.word.contains(letter) {
             GuessResult::Correct
@@ -164,6 +373,36 @@ impl HangmanGame {
         }
     }
 
+    pub fn guess_word(&mut self, guess: &str) -> GuessResult {
+        let guess = guess.trim().to_uppercase();
+
+        if !self.rules.allow_word_guesses || guess.is_empty() || !guess.chars().all(char::is_alphabetic) {
+            return GuessResult::InvalidInput;
+        }
+
+        // An adversarial game only concedes the word once no other candidate is left.
+        if let Some(family) = self.family.as_mut() {
+            if family.len() > 1 {
+                family.retain(|word| *word != guess);
+                if let Some(word) = family.first() {
+                    self.word = word.clone();
+                }
+            }
+        }
+
+        if guess == self.word {
+            self.guessed_letters.extend(guess.chars());
+            GuessResult::Correct
+        } else {
+            self.wrong_guesses = (self.wrong_guesses + self.rules.wrong_word_penalty).min(self.rules.max_wrong_guesses);
+            GuessResult::Wrong
+        }
+    }
+
+    pub fn forfeit_turn(&mut self) {
+        self.wrong_guesses = (self.wrong_guesses + 1).min(self.rules.max_wrong_guesses);
+    }
+
     pub fn get_guessed_letters(&self) -> Vec<char> {
         let mut letters: Vec<char> = self.guessed_letters.iter().copied().collect();
         letters.sort_unstable();
@@ -171,7 +410,7 @@ impl HangmanGame {
     }
 
     pub fn get_hint(&mut self) -> String {
-        if self.hints_used >= self.max_hints {
+        if self.hints_used >= self.rules.max_hints {
             return "No more hints available!".to_string();
         }
 
@@ -189,9 +428,13 @@ impl HangmanGame {
         let index = self.rng.gen_range(unguessed.len());
         let hint_letter = unguessed[index];
 
+        if let Some(family) = self.family.as_mut() {
+            family.retain(|word| word.contains(hint_letter));
+        }
+
         format!(
             "Hint {}/{}: The word contains the letter '{}'",
-            self.hints_used, self.max_hints, hint_letter
+            self.hints_used, self.rules.max_hints, hint_letter
         )
     }
 
@@ -203,11 +446,11 @@ impl HangmanGame {
 
         GameStats {
             revealed_letters: revealed,
-            total_letters: self.word.len(),
+            total_letters: self.word.chars().count(),
             wrong_guesses: self.wrong_guesses,
-            max_wrong: self.max_wrong,
+            max_wrong: self.rules.max_wrong_guesses,
             hints_used: self.hints_used,
-            max_hints: self.max_hints,
+            max_hints: self.rules.max_hints,
         }
     }
 
@@ -220,6 +463,45 @@ impl HangmanGame {
     }
 }
 
+mod scoring {
+    use super::*;
+
+    const TIME_BONUS_SECS: u64 = 120;
+    const POINTS_PER_RARITY: u32 = 10;
+    const WRONG_GUESS_COST: u32 = 5;
+    const HINT_COST: u32 = 15;
+
+    // Scrabble tile values stand in for how rare each letter is.
+    fn letter_value(letter: char) -> u32 {
+        match letter {
+            'A' | 'E' | 'I' | 'O' | 'U' | 'L' | 'N' | 'S' | 'T' | 'R' => 1,
+            'D' | 'G' => 2,
+            'B' | 'C' | 'M' | 'P' => 3,
+            'F' | 'H' | 'V' | 'W' | 'Y' => 4,
+            'K' => 5,
+            'J' | 'X' => 8,
+            'Q' | 'Z' => 10,
+            _ => 0,
+        }
+    }
+
+    pub fn rarity(word: &str) -> u32 {
+        word.chars().map(letter_value).sum()
+    }
+
+    pub fn score(word: &str, stats: &GameStats, rules: &RuleSet, elapsed: Duration) -> u32 {
+        if stats.revealed_letters < stats.total_letters {
+            return 0;
+        }
+
+        let base = rarity(word) * POINTS_PER_RARITY;
+        let time_bonus = TIME_BONUS_SECS.saturating_sub(elapsed.as_secs()) as u32;
+        let deductions = stats.wrong_guesses as u32 * WRONG_GUESS_COST + stats.hints_used as u32 * HINT_COST;
+
+        (base + time_bonus).saturating_sub(deductions) * rules.score_multiplier
+    }
+}
+
 mod hangman_art {
     const HANGMAN_STAGES: [&str; 7] = [
         "   ____\n   |  |\n   |\n   |\n   |\n___|___",
@@ -231,15 +513,60 @@ mod hangman_art {
         "   ____\n   |  |\n   |  O\n   | /|\\\n   | / \\\n___|___",
     ];
 
-    pub fn draw(wrong_guesses: usize) {
-        if let Some(stage) = HANGMAN_STAGES.get(wrong_guesses) {
-            println!("{}", stage);
-        }
+    pub fn stage(wrong_guesses: usize) -> &'static str {
+        HANGMAN_STAGES[wrong_guesses.min(HANGMAN_STAGES.len() - 1)]
     }
 }
 
 mod ui {
     use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    // std can't read stdin with a timeout, so one reader thread forwards lines over a channel
+    // and every prompt reads from it; reading stdin directly would race the thread for lines.
+    pub struct LineReader {
+        receiver: mpsc::Receiver<io::Result<String>>,
+    }
+
+    impl LineReader {
+        pub fn new(receiver: mpsc::Receiver<io::Result<String>>) -> Self {
+            Self { receiver }
+        }
+
+        fn stdin() -> Self {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                for line in io::stdin().lines() {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+            Self::new(receiver)
+        }
+
+        /// The next line, or `None` if `timeout` runs out first. End of input reads as an empty line.
+        pub fn next_line(&self, timeout: Option<Duration>) -> Option<String> {
+            let line = match timeout {
+                None => self.receiver.recv().ok(),
+                Some(timeout) => match self.receiver.recv_timeout(timeout) {
+                    Ok(line) => Some(line),
+                    Err(mpsc::RecvTimeoutError::Timeout) => return None,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => None,
+                },
+            };
+            Some(line.transpose().expect("Failed to read input").unwrap_or_default())
+        }
+    }
+
+    thread_local! {
+        static STDIN: LineReader = LineReader::stdin();
+    }
+
+    fn read_line(timeout: Option<Duration>) -> Option<String> {
+        STDIN.with(|stdin| stdin.next_line(timeout))
+    }
 
     pub fn clear_screen() {
         print!("\x1B[2J\x1B[1;1H");
@@ -249,44 +576,121 @@ mod ui {
     pub fn wait_for_enter() {
         print!("Press Enter to continue...");
         io::stdout().flush().unwrap();
-        let mut dummy = String::new();
-        io::stdin().read_line(&mut dummy).ok();
+        read_line(None);
     }
 
     pub fn get_input(prompt: &str) -> String {
+        get_timed_input(prompt, None).unwrap_or_default()
+    }
+
+    /// Like `get_input`, but gives up with `None` once `limit` passes without a line.
+    pub fn get_timed_input(prompt: &str, limit: Option<Duration>) -> Option<String> {
+        print!("{}", prompt);
+        io::stdout().flush().unwrap();
+
+        read_line(limit).map(|input| input.trim().to_uppercase())
+    }
+
+    pub fn get_raw_input(prompt: &str) -> String {
         print!("{}", prompt);
         io::stdout().flush().unwrap();
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).expect("Failed to read input");
+        read_line(None).unwrap_or_default().trim().to_string()
+    }
+
+    pub fn display_leaderboard(store: &profiles::ProfileStore) {
+        println!("=== LEADERBOARD ===");
+
+        let ranked = store.leaderboard();
+        if ranked.is_empty() {
+            println!("No games recorded yet.");
+            return;
+        }
+
+        println!(
+            "{:<4} {:<16} {:>5} {:>6} {:>7} {:>6} {:>10} {:>6}",
+            "#", "Player", "Wins", "Losses", "Streak", "Best", "Avg wrong", "Hints"
+        );
+        for (rank, profile) in ranked.iter().enumerate() {
+            println!(
+                "{:<4} {:<16} {:>5} {:>6} {:>7} {:>6} {:>10.2} {:>6.2}",
+                rank + 1,
+                profile.name,
+                profile.wins,
+                profile.losses,
+                profile.current_streak,
+                profile.best_streak,
+                profile.average_wrong_guesses(),
+                profile.average_hints_used()
+            );
+        }
+    }
+
+    pub fn get_hidden_input(prompt: &str) -> String {
+        print!("{}", prompt);
+        io::stdout().flush().unwrap();
+
+        let echo_disabled = set_terminal_echo(false);
+        let input = read_line(None).unwrap_or_default();
+        if echo_disabled {
+            set_terminal_echo(true);
+        }
+
+        // Scroll the word away in case the terminal could not hide it.
+        clear_screen();
         input.trim().to_uppercase()
     }
 
-    pub fn display_game_header() {
-        println!("=== INTERMEDIATE HANGMAN ===");
+    fn set_terminal_echo(enabled: bool) -> bool {
+        std::process::Command::new("stty")
+            .arg(if enabled { "echo" } else { "-echo" })
+            .stdin(std::process::Stdio::inherit())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
     }
 
-    pub fn display_game_state(game: &HangmanGame) {
-        display_game_header();
-        println!("Category: {} Words", game.get_category());
+    pub fn word_guess_feedback(word: &str, result: GuessResult) -> String {
+        match result {
+            GuessResult::Correct => format!("🎯 Yes! The word is '{}'!", word),
+            GuessResult::Wrong => format!("❌ Wrong guess! The word is not '{}'.", word),
+            GuessResult::AlreadyGuessed | GuessResult::InvalidInput => {
+                "Whole-word guesses must be letters only, and allowed at this difficulty!".to_string()
+            }
+        }
+    }
 
-        hangman_art::draw(game.wrong_guesses);
-        println!();
+    pub fn guess_feedback(letter: char, result: GuessResult) -> String {
+        match result {
+            GuessResult::Correct => format!("✅ Good guess! '{}' is in the word!", letter),
+            GuessResult::Wrong => format!("❌ Wrong guess! '{}' is not in the word.", letter),
+            GuessResult::AlreadyGuessed | GuessResult::InvalidInput => result.to_string(),
+        }
+    }
 
-        println!("Word: {}", game.display_word());
-        println!("{}", game.get_stats());
+    pub fn render_game_state(game: &HangmanGame) -> String {
+        let mut out = String::from("=== INTERMEDIATE HANGMAN ===\n");
+        out += &format!("Category: {} Words\n", game.get_category());
+        out += hangman_art::stage(game.wrong_guesses);
+        out += "\n\n";
+        out += &format!("Word: {}\n", game.display_word());
+        out += &format!("{}\n", game.get_stats());
 
         if !game.guessed_letters.is_empty() {
-            println!("Guessed letters: {:?}", game.get_guessed_letters());
+            out += &format!("Guessed letters: {:?}\n", game.get_guessed_letters());
         }
+        out
+    }
+
+    pub fn display_game_state(game: &HangmanGame) {
+        print!("{}", render_game_state(game));
     }
 }
 
 mod word_selector {
     use super::*;
 
-    pub fn get_random_word(words: &[&str]) -> String {
-        let mut rng = SimpleRng::new();
+    pub fn get_random_word(words: &[&str], rng: &mut dyn rng::RandomSource) -> String {
         let index = rng.gen_range(words.len());
         words[index].to_string()
     }
@@ -303,15 +707,363 @@ mod word_selector {
             match input.as_str() {
                 "1" => return &config::CATEGORIES[0],
                 "2" => return &config::CATEGORIES[1],
-                _ => println!("Please enter 1 or 2"),
+                "3" => return &config::CATEGORIES[2],
+                _ => println!("Please enter 1, 2 or 3"),
+            }
+        }
+    }
+
+    pub fn validate_secret_word(word: &str) -> Result<String, &'static str> {
+        let word = word.trim().to_uppercase();
+
+        if word.is_empty() {
+            Err("The secret word cannot be empty")
+        } else if !word.chars().all(char::is_alphabetic) {
+            Err("The secret word may only contain letters")
+        } else {
+            Ok(word)
+        }
+    }
+
+    pub fn read_secret_word() -> String {
+        loop {
+            let input = ui::get_hidden_input("Enter the secret word (hidden): ");
+            match validate_secret_word(&input) {
+                Ok(word) => return word,
+                Err(e) => println!("{}", e),
+            }
+        }
+    }
+}
+
+mod profiles {
+    use super::*;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct PlayerProfile {
+        pub name: String,
+        pub wins: usize,
+        pub losses: usize,
+        pub current_streak: usize,
+        pub best_streak: usize,
+        pub total_wrong_guesses: usize,
+        pub total_hints_used: usize,
+    }
+
+    impl PlayerProfile {
+        pub fn new(name: &str) -> Self {
+            Self {
+                name: sanitize_name(name),
+                ..Self::default()
+            }
+        }
+
+        pub fn games_played(&self) -> usize {
+            self.wins + self.losses
+        }
+
+        pub fn average_wrong_guesses(&self) -> f64 {
+            average(self.total_wrong_guesses, self.games_played())
+        }
+
+        pub fn average_hints_used(&self) -> f64 {
+            average(self.total_hints_used, self.games_played())
+        }
+
+        pub fn record(&mut self, won: bool, stats: &GameStats) {
+            if won {
+                self.wins += 1;
+                self.current_streak += 1;
+                self.best_streak = self.best_streak.max(self.current_streak);
+            } else {
+                self.losses += 1;
+                self.current_streak = 0;
+            }
+
+            self.total_wrong_guesses += stats.wrong_guesses;
+            self.total_hints_used += stats.hints_used;
+        }
+
+        fn to_line(&self) -> String {
+            format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                self.name,
+                self.wins,
+                self.losses,
+                self.current_streak,
+                self.best_streak,
+                self.total_wrong_guesses,
+                self.total_hints_used
+            )
+        }
+
+        fn from_line(line: &str) -> Option<Self> {
+            let mut fields = line.split('\t');
+            let name = fields.next()?.to_string();
+            let mut numbers = fields.map(|field| field.trim().parse::<usize>());
+
+            let mut next = || numbers.next()?.ok();
+            let profile = Self {
+                name,
+                wins: next()?,
+                losses: next()?,
+                current_streak: next()?,
+                best_streak: next()?,
+                total_wrong_guesses: next()?,
+                total_hints_used: next()?,
+            };
+
+            Some(profile)
+        }
+    }
+
+    impl fmt::Display for PlayerProfile {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(f, "Player: {}", self.name)?;
+            writeln!(f, "Record: {} wins, {} losses", self.wins, self.losses)?;
+            writeln!(f, "Streak: {} (best {})", self.current_streak, self.best_streak)?;
+            write!(
+                f,
+                "Averages: {:.2} wrong guesses, {:.2} hints per game",
+                self.average_wrong_guesses(),
+                self.average_hints_used()
+            )
+        }
+    }
+
+    fn average(total: usize, games: usize) -> f64 {
+        if games == 0 {
+            0.0
+        } else {
+            total as f64 / games as f64
+        }
+    }
+
+    fn sanitize_name(name: &str) -> String {
+        name.trim().replace(['\t', '\n', '\r'], " ")
+    }
+
+    #[derive(Debug)]
+    pub struct ProfileStore {
+        path: PathBuf,
+        profiles: Vec<PlayerProfile>,
+    }
+
+    impl ProfileStore {
+        pub fn new(path: impl AsRef<Path>) -> Self {
+            Self {
+                path: path.as_ref().to_path_buf(),
+                profiles: Vec::new(),
+            }
+        }
+
+        pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+            let mut store = Self::new(path);
+
+            match fs::read_to_string(&store.path) {
+                Ok(contents) => {
+                    store.profiles = contents.lines().filter_map(PlayerProfile::from_line).collect();
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+
+            Ok(store)
+        }
+
+        pub fn save(&self) -> io::Result<()> {
+            let contents: String = self
+                .profiles
+                .iter()
+                .map(|profile| profile.to_line() + "\n")
+                .collect();
+            fs::write(&self.path, contents)
+        }
+
+        pub fn profile_mut(&mut self, name: &str) -> &mut PlayerProfile {
+            let name = sanitize_name(name);
+            let index = match self.profiles.iter().position(|p| p.name.eq_ignore_ascii_case(&name)) {
+                Some(index) => index,
+                None => {
+                    self.profiles.push(PlayerProfile::new(&name));
+                    self.profiles.len() - 1
+                }
+            };
+            &mut self.profiles[index]
+        }
+
+        pub fn leaderboard(&self) -> Vec<&PlayerProfile> {
+            let mut ranked: Vec<&PlayerProfile> = self.profiles.iter().collect();
+            ranked.sort_by(|a, b| {
+                b.wins
+                    .cmp(&a.wins)
+                    .then(b.best_streak.cmp(&a.best_streak))
+                    .then(a.average_wrong_guesses().total_cmp(&b.average_wrong_guesses()))
+                    .then(a.name.cmp(&b.name))
+            });
+            ranked
+        }
+    }
+}
+
+mod solver {
+    use super::*;
+    use std::collections::HashMap;
+
+    const FALLBACK_ORDER: &str = "ETAOINSHRDLCUMWFGYPBVKJXQZ";
+
+    #[derive(Debug, Clone)]
+    pub struct HangmanSolver {
+        dictionary: Vec<String>,
+    }
+
+    impl HangmanSolver {
+        pub fn new<S: AsRef<str>>(words: &[S]) -> Self {
+            let mut dictionary: Vec<String> = words.iter().map(|w| w.as_ref().to_uppercase()).collect();
+            dictionary.sort_unstable();
+            dictionary.dedup();
+            Self { dictionary }
+        }
+
+        pub fn from_categories(categories: &[WordCategory]) -> Self {
+            let words: Vec<&str> = categories.iter().flat_map(|c| c.words.iter().copied()).collect();
+            Self::new(&words)
+        }
+
+        pub fn candidates(&self, masked: &str, guessed: &[char]) -> Vec<&str> {
+            let pattern = parse_mask(masked);
+
+            self.dictionary
+                .iter()
+                .map(String::as_str)
+                .filter(|word| matches_pattern(word, &pattern, guessed))
+                .collect()
+        }
+
+        pub fn next_guess(&self, masked: &str, guessed: &[char]) -> Option<char> {
+            let candidates = self.candidates(masked, guessed);
+
+            let mut letters: Vec<char> = candidates
+                .iter()
+                .flat_map(|word| word.chars())
+                .filter(|c| !guessed.contains(c))
+                .collect();
+            letters.sort_unstable();
+            letters.dedup();
+
+            let best = letters
+                .into_iter()
+                .map(|letter| (letter, information(&candidates, letter), coverage(&candidates, letter)))
+                .max_by(|a, b| {
+                    a.1.total_cmp(&b.1)
+                        .then(a.2.cmp(&b.2))
+                        .then(b.0.cmp(&a.0))
+                })
+                .map(|(letter, _, _)| letter);
+
+            best.or_else(|| FALLBACK_ORDER.chars().find(|c| !guessed.contains(c)))
+        }
+    }
+
+    fn parse_mask(masked: &str) -> Vec<Option<char>> {
+        masked
+            .split_whitespace()
+            .map(|token| token.chars().next().filter(|&c| c != '_'))
+            .collect()
+    }
+
+    fn matches_pattern(word: &str, pattern: &[Option<char>], guessed: &[char]) -> bool {
+        word.chars().count() == pattern.len()
+            && word.chars().zip(pattern).all(|(c, slot)| match slot {
+                Some(revealed) => c == *revealed,
+                None => !guessed.contains(&c),
+            })
+    }
+
+    fn coverage(candidates: &[&str], letter: char) -> usize {
+        candidates.iter().filter(|word| word.contains(letter)).count()
+    }
+
+    // Shannon entropy of the families the candidates split into when `letter` is guessed.
+    fn information(candidates: &[&str], letter: char) -> f64 {
+        let mut families: HashMap<Vec<usize>, usize> = HashMap::new();
+        for word in candidates {
+            let positions = word
+                .chars()
+                .enumerate()
+                .filter(|&(_, c)| c == letter)
+                .map(|(i, _)| i)
+                .collect();
+            *families.entry(positions).or_insert(0) += 1;
+        }
+
+        let total = candidates.len() as f64;
+        families
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    pub fn play(solver: &HangmanSolver, game: &mut HangmanGame) -> bool {
+        while !game.is_won() && !game.is_lost() {
+            let Some(letter) = solver.next_guess(&game.display_word(), &game.get_guessed_letters()) else {
+                break;
+            };
+            game.make_guess(letter);
+        }
+        game.is_won()
+    }
+
+    #[derive(Debug, Clone, Default)]
+    pub struct BenchmarkReport {
+        pub games: usize,
+        pub wins: usize,
+        pub total_wrong_guesses: usize,
+    }
+
+    impl BenchmarkReport {
+        pub fn average_wrong_guesses(&self) -> f64 {
+            if self.games == 0 {
+                0.0
+            } else {
+                self.total_wrong_guesses as f64 / self.games as f64
             }
         }
     }
+
+    impl fmt::Display for BenchmarkReport {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(f, "Games played: {}", self.games)?;
+            writeln!(f, "Games won: {}/{}", self.wins, self.games)?;
+            write!(f, "Average wrong guesses: {:.2}", self.average_wrong_guesses())
+        }
+    }
+
+    pub fn benchmark(solver: &HangmanSolver, words: &[&str]) -> BenchmarkReport {
+        let mut report = BenchmarkReport::default();
+
+        for word in words {
+            let mut game = HangmanGame::new(word, "Benchmark");
+            if play(solver, &mut game) {
+                report.wins += 1;
+            }
+            report.games += 1;
+            report.total_wrong_guesses += game.get_stats().wrong_guesses;
+        }
+
+        report
+    }
 }
 
 #[derive(Debug, PartialEq)]
 enum Command {
     Guess(char),
+    GuessWord(String),
     Hint,
     Quit,
     Invalid,
@@ -319,10 +1071,14 @@ enum Command {
 
 impl From<&str> for Command {
     fn from(input: &str) -> Self {
+        if let Some(word) = input.strip_prefix("GUESS ") {
+            return Self::GuessWord(word.trim().to_string());
+        }
+
         match input {
             "HINT" => Self::Hint,
             "QUIT" => Self::Quit,
-            s if s.len() == 1 => {
+            s if s.chars().count() == 1 => {
                 if let Some(c) = s.chars().next() {
                     Self::Guess(c)
                 } else {
@@ -336,36 +1092,36 @@ impl From<&str> for Command {
 
 struct GameController {
     game: HangmanGame,
+    started: Instant,
 }
 
 impl GameController {
     fn new(game: HangmanGame) -> Self {
-        Self { game }
+        Self { game, started: Instant::now() }
     }
 
     fn display_intro(&self) {
         println!("\nWelcome to Intermediate Hangman!");
         println!("Category: {} Words", self.game.get_category());
-        println!("Word length: {} letters", self.game.get_word().len());
-        println!("Available hints: {}", self.game.max_hints);
-        println!("Commands: letter to guess, 'hint' for a hint, 'quit' to exit\n");
+        println!("Word length: {} letters", self.game.get_word().chars().count());
+        println!("{}", self.game.rules());
+        println!("Commands: letter to guess, 'guess <word>' for the whole word, 'hint' for a hint, 'quit' to exit\n");
     }
 
     fn handle_command(&mut self, command: Command) -> bool {
         match command {
             Command::Guess(letter) => {
                 let result = self.game.make_guess(letter);
-                match result {
-                    GuessResult::Correct => {
-                        println!("✅ Good guess! '{}' is in the word!", letter);
-                    }
-                    GuessResult::Wrong => {
-                        println!("❌ Wrong guess! '{}' is not in the word.", letter);
-                    }
-                    GuessResult::AlreadyGuessed | GuessResult::InvalidInput => {
-                        println!("{}", result);
-                    }
+                println!("{}", ui::guess_feedback(letter, result));
+
+                if !self.game.is_won() && !self.game.is_lost() {
+                    ui::wait_for_enter();
                 }
+                false
+            }
+            Command::GuessWord(word) => {
+                let result = self.game.guess_word(&word);
+                println!("{}", ui::word_guess_feedback(&word, result));
 
                 if !self.game.is_won() && !self.game.is_lost() {
                     ui::wait_for_enter();
@@ -399,6 +1155,8 @@ impl GameController {
                 "Final stats: {}/{} wrong guesses, {}/{} hints used",
                 stats.wrong_guesses, stats.max_wrong, stats.hints_used, stats.max_hints
             );
+            let score = scoring::score(self.game.get_word(), &stats, self.game.rules(), self.started.elapsed());
+            println!("Score: {} points", score);
             return true;
         }
 
@@ -411,6 +1169,28 @@ impl GameController {
         false
     }
 
+    fn run_computer(&mut self, solver: &solver::HangmanSolver) {
+        self.display_intro();
+
+        loop {
+            ui::clear_screen();
+            ui::display_game_state(&self.game);
+
+            if self.check_end_conditions() {
+                break;
+            }
+
+            let Some(letter) = solver.next_guess(&self.game.display_word(), &self.game.get_guessed_letters()) else {
+                println!("🤖 The computer has run out of letters to try.");
+                break;
+            };
+
+            println!("\n🤖 Computer guesses '{}'", letter);
+            println!("{}", self.game.make_guess(letter));
+            ui::wait_for_enter();
+        }
+    }
+
     fn run(&mut self) {
         self.display_intro();
 
@@ -422,7 +1202,16 @@ impl GameController {
                 break;
             }
 
-            let input = ui::get_input("\nEnter a letter, 'hint', or 'quit':\n");
+            let limit = self.game.rules().turn_time_limit;
+            let prompt = "\nEnter a letter, 'guess <word>', 'hint', or 'quit':\n";
+            let Some(input) = ui::get_timed_input(prompt, limit) else {
+                let limit = limit.unwrap_or_default();
+                println!("\n⏰ Too slow! Turns are limited to {}s, that costs you a wrong guess.", limit.as_secs());
+                self.game.forfeit_turn();
+                ui::wait_for_enter();
+                continue;
+            };
+
             let command = Command::from(input.as_str());
 
             if self.handle_command(command) {
@@ -434,16 +1223,287 @@ impl GameController {
     }
 }
 
+mod network {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+    // Line protocol: the host owns the `HangmanGame`; the guesser only renders text and forwards input.
+    const MSG: &str = "MSG ";
+    const CLEAR: &str = "CLEAR";
+    const PROMPT: &str = "PROMPT ";
+    const END: &str = "END";
+
+    fn send_text(writer: &mut impl Write, text: &str) -> io::Result<()> {
+        for line in text.lines() {
+            writeln!(writer, "{}{}", MSG, line)?;
+        }
+        Ok(())
+    }
+
+    pub fn serve_guesser(game: &mut HangmanGame, mut reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+        loop {
+            writeln!(writer, "{}", CLEAR)?;
+            send_text(&mut writer, &ui::render_game_state(game))?;
+
+            if game.is_won() || game.is_lost() {
+                let verdict = if game.is_won() { "🎉 You won!" } else { "💀 You've been hanged!" };
+                send_text(&mut writer, &format!("\n{}\nThe word was: {}", verdict, game.get_word()))?;
+                println!("Guesser finished: {} (word: {})", verdict, game.get_word());
+                break;
+            }
+
+            writeln!(writer, "{}Enter a letter, 'guess <word>', 'hint', or 'quit':", PROMPT)?;
+            writer.flush()?;
+
+            // The host sets a socket read timeout from the turn limit, so a silent guesser
+            // surfaces here as WouldBlock (Unix) or TimedOut (Windows) instead of stalling.
+            let turn_started = Instant::now();
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) => {
+                    println!("Guesser disconnected.");
+                    return Ok(());
+                }
+                Ok(_) => {}
+                Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => line.clear(),
+                Err(e) => return Err(e),
+            }
+
+            if let Some(limit) = game.rules().turn_time_limit {
+                if line.is_empty() || turn_started.elapsed() > limit {
+                    game.forfeit_turn();
+                    println!("Guesser ran out of time.");
+                    send_text(&mut writer, &format!("⏰ Too slow! Turns are limited to {}s, that costs you a wrong guess.", limit.as_secs()))?;
+                    continue;
+                }
+            }
+
+            let input = line.trim().to_uppercase();
+            let feedback = match Command::from(input.as_str()) {
+                Command::Guess(letter) => ui::guess_feedback(letter, game.make_guess(letter)),
+                Command::GuessWord(word) => ui::word_guess_feedback(&word, game.guess_word(&word)),
+                Command::Hint => format!("💡 {}", game.get_hint()),
+                Command::Quit => {
+                    send_text(&mut writer, &format!("Thanks for playing! The word was: {}", game.get_word()))?;
+                    println!("Guesser quit.");
+                    break;
+                }
+                Command::Invalid => "Please enter a letter!".to_string(),
+            };
+
+            println!("Guesser: {} → {}", input, feedback);
+            send_text(&mut writer, &feedback)?;
+        }
+
+        writeln!(writer, "{}", END)?;
+        writer.flush()
+    }
+
+    pub fn host(game: &mut HangmanGame, addr: impl ToSocketAddrs) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        println!("Waiting for a guesser on {} ...", listener.local_addr()?);
+
+        let (stream, peer) = listener.accept()?;
+        println!("Guesser connected from {}", peer);
+
+        stream.set_read_timeout(game.rules().turn_time_limit)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        serve_guesser(game, reader, stream)
+    }
+
+    pub fn join(addr: impl ToSocketAddrs) -> io::Result<()> {
+        let stream = TcpStream::connect(addr)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+
+        for line in reader.lines() {
+            let line = line?;
+
+            if let Some(text) = line.strip_prefix(MSG) {
+                println!("{}", text);
+            } else if let Some(prompt) = line.strip_prefix(PROMPT) {
+                let input = ui::get_input(&format!("\n{}\n", prompt));
+                writeln!(writer, "{}", input)?;
+            } else if line == CLEAR {
+                ui::clear_screen();
+            } else if line == END {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn run_benchmark(solver: &solver::HangmanSolver) {
+    println!("=== HANGMAN SOLVER BENCHMARK ===");
+
+    for category in config::CATEGORIES.iter() {
+        let report = solver::benchmark(solver, category.words);
+        println!("\n{} pack ({} words)", category.name, category.words.len());
+        println!("{}", report);
+    }
+}
+
+fn parse_seed(args: &[String]) -> Result<Option<u64>, String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = if arg == "--seed" {
+            iter.next().ok_or("--seed requires a value")?
+        } else if let Some(value) = arg.strip_prefix("--seed=") {
+            value
+        } else {
+            continue;
+        };
+
+        return value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("Invalid seed '{}': expected a non-negative integer", value));
+    }
+    Ok(None)
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let index = args.iter().position(|arg| arg == flag)?;
+    args.get(index + 1)
+        .map(String::as_str)
+        .filter(|value| !value.starts_with("--"))
+}
+
+fn host_addr(args: &[String]) -> &str {
+    flag_value(args, "--host").unwrap_or(if args.iter().any(|arg| arg == "--public") {
+        config::PUBLIC_HOST_ADDR
+    } else {
+        config::HOST_ADDR
+    })
+}
+
+fn new_round(session_rng: &mut rng::SplitMix64) -> HangmanGame {
+    new_game(word_selector::choose_difficulty(), session_rng)
+}
+
+fn new_game(category: &WordCategory, session_rng: &mut rng::SplitMix64) -> HangmanGame {
+    let mut rng = session_rng.fork();
+
+    let game = if category.adversarial {
+        HangmanGame::new_adversarial(category.words, category.name)
+    } else {
+        let word = word_selector::get_random_word(category.words, &mut rng);
+        HangmanGame::new(&word, category.name)
+    };
+    game.with_rules(category.rules).with_rng(rng)
+}
+
+fn run_session(
+    store: &mut profiles::ProfileStore,
+    player: &str,
+    rng: &mut rng::SplitMix64,
+) {
+    loop {
+        let mut controller = GameController::new(new_round(rng));
+        controller.run();
+
+        // A round abandoned with 'quit' counts as a loss.
+        let profile = store.profile_mut(player);
+        profile.record(controller.game.is_won(), &controller.game.get_stats());
+        println!("\n{}", profile);
+
+        if let Err(e) = store.save() {
+            println!("⚠️  Could not save profiles: {}", e);
+        }
+
+        loop {
+            match ui::get_input("\nPlay again? (y)es, (l)eaderboard, (n)o: ").as_str() {
+                "Y" | "YES" => break,
+                "L" | "LEADERBOARD" => {
+                    ui::clear_screen();
+                    ui::display_leaderboard(store);
+                }
+                "N" | "NO" => return,
+                _ => println!("Please enter y, l or n"),
+            }
+        }
+    }
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let solver = solver::HangmanSolver::from_categories(&config::CATEGORIES);
+
+    if args.iter().any(|arg| arg == "--benchmark") {
+        run_benchmark(&solver);
+        return;
+    }
+
+    let mut store = profiles::ProfileStore::load(config::PROFILES_FILE).unwrap_or_else(|e| {
+        println!("⚠️  Could not load profiles ({}), starting fresh.", e);
+        profiles::ProfileStore::new(config::PROFILES_FILE)
+    });
+
+    if args.iter().any(|arg| arg == "--leaderboard") {
+        ui::display_leaderboard(&store);
+        return;
+    }
+
+    // In daily mode the whole session is seeded from the date: every player gets the same
+    // sequence of words that day, but each round still draws a new one.
+    let daily = args.iter().any(|arg| arg == "--daily").then(rng::today);
+    let mut rng = match parse_seed(&args).map(|seed| daily.or(seed)) {
+        Ok(Some(seed)) => rng::SplitMix64::from_seed(seed),
+        Ok(None) => rng::SplitMix64::from_entropy(),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
     println!("=== INTERMEDIATE HANGMAN ===");
     println!("Features: Hints, categories, better organization\n");
 
-    let category = word_selector::choose_difficulty();
-    let word = word_selector::get_random_word(category.words);
-    let game = HangmanGame::new(&word, category.name);
+    if let Some(day) = daily {
+        println!("📅 Daily word for {}\n", rng::format_day(day));
+    }
+
+    if args.iter().any(|arg| arg == "--computer") {
+        let mut controller = GameController::new(new_round(&mut rng));
+        controller.run_computer(&solver);
+        return;
+    }
+
+    if args.iter().any(|arg| arg == "--join") {
+        let addr = flag_value(&args, "--join").unwrap_or(config::JOIN_ADDR);
+        if let Err(e) = network::join(addr) {
+            eprintln!("Could not play on {}: {}", addr, e);
+        }
+        return;
+    }
 
-    let mut controller = GameController::new(game);
-    controller.run();
+    if args.iter().any(|arg| arg == "--two-player" || arg == "--host") {
+        println!("Player 1, choose a word for the guesser.");
+        let word = word_selector::read_secret_word();
+        let mut game = HangmanGame::new(&word, "Secret").with_rng(rng.fork());
+
+        if args.iter().any(|arg| arg == "--host") {
+            let addr = host_addr(&args);
+            if let Err(e) = network::host(&mut game, addr) {
+                eprintln!("Could not host on {}: {}", addr, e);
+            }
+        } else {
+            GameController::new(game).run();
+        }
+        return;
+    }
+
+    let player = loop {
+        let name = ui::get_raw_input("Player name: ");
+        if !name.is_empty() {
+            break name;
+        }
+    };
+
+    run_session(&mut store, &player, &mut rng);
 }
 
 #[cfg(test)]
@@ -455,7 +1515,7 @@ mod tests {
         let game = HangmanGame::new("TEST", "Easy");
         assert_eq!(game.word, "TEST");
         assert_eq!(game.wrong_guesses, 0);
-        assert_eq!(game.max_wrong, 6);
+        assert_eq!(game.rules().max_wrong_guesses, 6);
         assert!(!game.is_won());
         assert!(!game.is_lost());
     }
@@ -505,4 +1565,317 @@ mod tests {
         assert_eq!(stats.total_letters, 4);
         assert_eq!(stats.wrong_guesses, 0);
     }
+
+    #[test]
+    fn test_solver_filters_candidates() {
+        let solver = solver::HangmanSolver::new(&["CAT", "CAR", "DOG", "BOOK"]);
+        let candidates = solver.candidates("C A _ ", &['C', 'A', 'T']);
+        assert_eq!(candidates, vec!["CAR"]);
+    }
+
+    #[test]
+    fn test_solver_wins_dictionary_word() {
+        let solver = solver::HangmanSolver::from_categories(&config::CATEGORIES);
+        let mut game = HangmanGame::new("ELEPHANT", "Medium");
+        assert!(solver::play(&solver, &mut game));
+        assert!(game.get_stats().wrong_guesses < config::MAX_WRONG_GUESSES);
+    }
+
+    #[test]
+    fn test_benchmark_report() {
+        let solver = solver::HangmanSolver::from_categories(&config::CATEGORIES);
+        let report = solver::benchmark(&solver, config::EASY_WORDS);
+        assert_eq!(report.games, config::EASY_WORDS.len());
+        assert_eq!(report.wins, report.games);
+    }
+
+    #[test]
+    fn test_adversarial_game_dodges_guesses() {
+        let mut game = HangmanGame::new_adversarial(&["CAT", "DOG", "SUN", "CAR"], "Evil");
+        assert!(game.is_adversarial());
+        assert_eq!(game.make_guess('C'), GuessResult::Wrong);
+        assert_eq!(game.make_guess('D'), GuessResult::Wrong);
+        assert_eq!(game.get_word(), "SUN");
+        assert_eq!(game.get_stats().wrong_guesses, 2);
+    }
+
+    #[test]
+    fn test_adversarial_word_matches_revealed_pattern() {
+        let mut game = HangmanGame::new_adversarial(config::EVIL_WORDS, "Evil");
+        for letter in "EAKOTRBN".chars() {
+            game.make_guess(letter);
+        }
+
+        let word = game.get_word();
+        let guessed = game.get_guessed_letters();
+        let revealed: String = word
+            .chars()
+            .map(|c| if guessed.contains(&c) { format!("{} ", c) } else { "_ ".to_string() })
+            .collect();
+        assert_eq!(revealed, game.display_word());
+        assert_eq!(game.get_stats().total_letters, 4);
+    }
+
+    fn finished_stats(wrong_guesses: usize, hints_used: usize) -> GameStats {
+        GameStats {
+            revealed_letters: 4,
+            total_letters: 4,
+            wrong_guesses,
+            max_wrong: config::MAX_WRONG_GUESSES,
+            hints_used,
+            max_hints: config::MAX_HINTS,
+        }
+    }
+
+    #[test]
+    fn test_profile_streaks_and_averages() {
+        let mut profile = profiles::PlayerProfile::new("Ada");
+        profile.record(true, &finished_stats(1, 0));
+        profile.record(true, &finished_stats(3, 2));
+        profile.record(false, &finished_stats(6, 1));
+        profile.record(true, &finished_stats(2, 0));
+
+        assert_eq!(profile.wins, 3);
+        assert_eq!(profile.losses, 1);
+        assert_eq!(profile.current_streak, 1);
+        assert_eq!(profile.best_streak, 2);
+        assert_eq!(profile.average_wrong_guesses(), 3.0);
+        assert_eq!(profile.average_hints_used(), 0.75);
+    }
+
+    #[test]
+    fn test_profile_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("hangman_profiles_{}.txt", std::process::id()));
+
+        let mut store = profiles::ProfileStore::load(&path).unwrap();
+        store.profile_mut("Ada").record(true, &finished_stats(1, 0));
+        store.profile_mut("Grace").record(true, &finished_stats(0, 0));
+        store.profile_mut("grace").record(true, &finished_stats(2, 1));
+        store.save().unwrap();
+
+        let reloaded = profiles::ProfileStore::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let names: Vec<&str> = reloaded.leaderboard().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Grace", "Ada"]);
+        assert_eq!(reloaded.leaderboard()[0].wins, 2);
+    }
+
+    #[test]
+    fn test_gen_range_stays_in_bounds() {
+        let mut rng = rng::SplitMix64::from_seed(7);
+        for max in 1..50 {
+            assert!(rng::RandomSource::gen_range(&mut rng, max) < max);
+        }
+    }
+
+    #[test]
+    fn test_seeded_games_are_reproducible() {
+        let play = |seed| {
+            let mut rng = rng::SplitMix64::from_seed(seed);
+            let word = word_selector::get_random_word(config::MEDIUM_WORDS, &mut rng);
+            let mut game = HangmanGame::new(&word, "Medium").with_rng(rng.fork());
+            (word, game.get_hint(), game.get_hint())
+        };
+
+        assert_eq!(play(42), play(42));
+    }
+
+    #[test]
+    fn test_daily_session_draws_a_new_word_each_round() {
+        let rounds = |day| {
+            let mut session_rng = rng::SplitMix64::from_seed(day);
+            (0..5)
+                .map(|_| new_game(&config::CATEGORIES[1], &mut session_rng).get_word().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let words = rounds(20_744);
+        assert_eq!(words, rounds(20_744));
+        assert!(words.iter().any(|word| *word != words[0]));
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(parse_seed(&args(&["--seed", "42"])), Ok(Some(42)));
+        assert_eq!(parse_seed(&args(&["--daily", "--seed=7"])), Ok(Some(7)));
+        assert_eq!(parse_seed(&args(&["--computer"])), Ok(None));
+        assert!(parse_seed(&args(&["--seed"])).is_err());
+        assert!(parse_seed(&args(&["--seed", "abc"])).is_err());
+    }
+
+    #[test]
+    fn test_format_day() {
+        assert_eq!(rng::format_day(0), "1970-01-01");
+        assert_eq!(rng::format_day(20_744), "2026-10-18");
+    }
+
+    #[test]
+    fn test_validate_secret_word() {
+        assert_eq!(word_selector::validate_secret_word(" rust "), Ok("RUST".to_string()));
+        assert!(word_selector::validate_secret_word("").is_err());
+        assert!(word_selector::validate_secret_word("R2D2").is_err());
+    }
+
+    #[test]
+    fn test_flag_value() {
+        let args: Vec<String> = ["--host", "--seed", "4", "--join", "10.0.0.2:7878"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(flag_value(&args, "--host"), None);
+        assert_eq!(flag_value(&args, "--join"), Some("10.0.0.2:7878"));
+    }
+
+    #[test]
+    fn test_host_addr_is_local_unless_public() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(host_addr(&args(&["--host"])), "127.0.0.1:7878");
+        assert_eq!(host_addr(&args(&["--host", "--public"])), "0.0.0.0:7878");
+        assert_eq!(host_addr(&args(&["--host", "192.168.1.5:9000"])), "192.168.1.5:9000");
+    }
+
+    #[test]
+    fn test_serve_guesser_over_protocol() {
+        let mut game = HangmanGame::new("HI", "Secret");
+        let input = io::Cursor::new("h\nx\ni\n");
+        let mut output = Vec::new();
+
+        network::serve_guesser(&mut game, input, &mut output).unwrap();
+
+        let transcript = String::from_utf8(output).unwrap();
+        assert!(game.is_won());
+        assert_eq!(game.get_stats().wrong_guesses, 1);
+        assert!(transcript.contains("MSG ❌ Wrong guess! 'X' is not in the word."));
+        assert!(transcript.contains("MSG The word was: HI"));
+        assert!(transcript.ends_with("END\n"));
+    }
+
+    #[test]
+    fn test_non_ascii_secret_word_can_be_won() {
+        let word = word_selector::validate_secret_word("café").unwrap();
+        let mut game = HangmanGame::new(&word, "Secret");
+        let input = io::Cursor::new("c\nf\né\na\n");
+        let mut output = Vec::new();
+
+        network::serve_guesser(&mut game, input, &mut output).unwrap();
+
+        assert_eq!(Command::from("É"), Command::Guess('É'));
+        assert!(game.is_won());
+        assert_eq!(game.get_stats().total_letters, 4);
+        assert_eq!(game.get_stats().wrong_guesses, 0);
+    }
+
+    #[test]
+    fn test_word_guess_command_parsing() {
+        assert_eq!(Command::from("GUESS ELEPHANT"), Command::GuessWord("ELEPHANT".to_string()));
+        assert_eq!(Command::from("GUESS"), Command::Invalid);
+    }
+
+    #[test]
+    fn test_word_guesses_and_penalty() {
+        let mut game = HangmanGame::new("GARDEN", "Medium").with_rules(config::MEDIUM_RULES);
+        assert_eq!(game.guess_word("KITCHEN"), GuessResult::Wrong);
+        assert_eq!(game.get_stats().wrong_guesses, config::MEDIUM_RULES.wrong_word_penalty);
+        assert_eq!(game.guess_word("garden"), GuessResult::Correct);
+        assert!(game.is_won());
+
+        let strict = RuleSet { allow_word_guesses: false, ..RuleSet::default() };
+        let mut game = HangmanGame::new("GARDEN", "Medium").with_rules(strict);
+        assert_eq!(game.guess_word("GARDEN"), GuessResult::InvalidInput);
+        assert!(!game.is_won());
+    }
+
+    #[test]
+    fn test_rules_per_difficulty() {
+        let mut game = HangmanGame::new("CAT", "Easy").with_rules(config::EASY_RULES);
+        for letter in "BDEFGHIJ".chars() {
+            game.make_guess(letter);
+        }
+        assert!(game.is_lost());
+        assert_eq!(game.get_stats().max_hints, 3);
+    }
+
+    #[test]
+    fn test_adversarial_word_guess_is_dodged() {
+        let mut game = HangmanGame::new_adversarial(&["BAKE", "CAKE"], "Evil");
+        assert_eq!(game.guess_word("BAKE"), GuessResult::Wrong);
+        assert_eq!(game.get_word(), "CAKE");
+        assert_eq!(game.guess_word("CAKE"), GuessResult::Correct);
+    }
+
+    #[test]
+    fn test_scoring_rewards_rarity_and_speed() {
+        let mut quiz = HangmanGame::new("QUIZ", "Easy");
+        quiz.guess_word("QUIZ");
+        let mut tree = HangmanGame::new("TREE", "Easy");
+        tree.guess_word("TREE");
+
+        let rules = RuleSet::default();
+        let fast = Duration::from_secs(10);
+        let quiz_score = scoring::score("QUIZ", &quiz.get_stats(), &rules, fast);
+        let tree_score = scoring::score("TREE", &tree.get_stats(), &rules, fast);
+        assert!(quiz_score > tree_score);
+        assert!(scoring::score("TREE", &tree.get_stats(), &rules, Duration::from_secs(600)) < tree_score);
+
+        let lost = HangmanGame::new("TREE", "Easy");
+        assert_eq!(scoring::score("TREE", &lost.get_stats(), &rules, fast), 0);
+    }
+
+    struct SilentGuesser {
+        replies: std::collections::VecDeque<io::Result<&'static str>>,
+    }
+
+    impl io::Read for SilentGuesser {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.replies.pop_front() {
+                Some(Ok(reply)) => {
+                    buf[..reply.len()].copy_from_slice(reply.as_bytes());
+                    Ok(reply.len())
+                }
+                Some(Err(e)) => Err(e),
+                None => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_silent_guesser_forfeits_turns() {
+        let mut game = HangmanGame::new("HI", "Medium").with_rules(config::MEDIUM_RULES);
+        let timeout = || Err(io::Error::from(io::ErrorKind::WouldBlock));
+        let replies = [Ok("h\n"), timeout(), Ok("i\n")].into_iter().collect();
+        let mut output = Vec::new();
+
+        network::serve_guesser(&mut game, io::BufReader::new(SilentGuesser { replies }), &mut output).unwrap();
+
+        let transcript = String::from_utf8(output).unwrap();
+        assert!(game.is_won());
+        assert_eq!(game.get_stats().wrong_guesses, 1);
+        assert!(transcript.contains("MSG ⏰ Too slow!"));
+
+        let mut game = HangmanGame::new("HI", "Medium").with_rules(config::MEDIUM_RULES);
+        for _ in 0..config::MEDIUM_RULES.max_wrong_guesses + 2 {
+            game.forfeit_turn();
+        }
+        assert_eq!(game.get_stats().wrong_guesses, config::MEDIUM_RULES.max_wrong_guesses);
+        assert!(game.is_lost());
+    }
+
+    #[test]
+    fn test_line_reader_times_out_idle_players() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let reader = ui::LineReader::new(receiver);
+        let short = Some(Duration::from_millis(10));
+
+        sender.send(Ok("a".to_string())).unwrap();
+        assert_eq!(reader.next_line(short).as_deref(), Some("a"));
+        assert_eq!(reader.next_line(short), None);
+
+        sender.send(Ok("b".to_string())).unwrap();
+        assert_eq!(reader.next_line(None).as_deref(), Some("b"));
+
+        drop(sender);
+        assert_eq!(reader.next_line(short).as_deref(), Some(""));
+    }
 }
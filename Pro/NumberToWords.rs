@@ -1,12 +1,17 @@
-use std::fmt;
-use std::io::{self, Write};
+use std::fmt::{self, Write as _};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::ops::Range;
 use std::str::FromStr;
+use std::time::Instant;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConversionError {
-    NumberTooLarge { value: u64, max: u64 },
+    NumberTooLarge { value: u128, max: u128 },
     InvalidInput(String),
     IoError(String),
+    Unsupported { language: Language, feature: String },
+    ParseError { position: usize, message: String },
 }
 
 impl fmt::Display for ConversionError {
@@ -17,6 +22,12 @@ impl fmt::Display for ConversionError {
             }
             Self::InvalidInput(input) => write!(f, "Invalid input: '{}'", input),
             Self::IoError(msg) => write!(f, "I/O error: {}", msg),
+            Self::Unsupported { language, feature } => {
+                write!(f, "{} is not supported for {:?}", feature, language)
+            }
+            Self::ParseError { position, message } => {
+                write!(f, "Parse error at position {}: {}", position, message)
+            }
         }
     }
 }
@@ -28,29 +39,150 @@ pub type Result<T> = std::result::Result<T, ConversionError>;
 pub trait NumberToWords<T> {
     fn to_words(&self, num: T) -> Result<String>;
     fn max_value(&self) -> T;
+
+    /// Writes the words for `num` into `out`, skipping the intermediate `String` where the
+    /// converter supports it.
+    fn write_words(&self, num: T, out: &mut dyn fmt::Write) -> Result<()> {
+        let words = self.to_words(num)?;
+        out.write_str(&words).map_err(|_| formatter_error())
+    }
+
+    /// Like `write_words`, for byte sinks. Words arrive in small pieces, so wrap unbuffered
+    /// writers in a `BufWriter`.
+    fn write_words_io(&self, num: T, out: &mut dyn io::Write) -> Result<()> {
+        let mut adapter = IoAdapter { inner: out, error: None };
+        self.write_words(num, &mut adapter).map_err(|e| match adapter.error.take() {
+            Some(io_error) => ConversionError::IoError(io_error.to_string()),
+            None => e,
+        })
+    }
+}
+
+fn formatter_error() -> ConversionError {
+    ConversionError::IoError("formatter error".to_string())
+}
+
+struct IoAdapter<'a> {
+    inner: &'a mut dyn io::Write,
+    error: Option<io::Error>,
+}
+
+impl fmt::Write for IoAdapter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+// Upper-cases the first character that passes through, so capitalization needs no buffer.
+struct CapitalizeFirst<'a> {
+    inner: &'a mut dyn fmt::Write,
+    pending: bool,
+}
+
+impl fmt::Write for CapitalizeFirst<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut chars = s.chars();
+        match chars.next() {
+            Some(first) if self.pending => {
+                self.pending = false;
+                for upper in first.to_uppercase() {
+                    self.inner.write_char(upper)?;
+                }
+                self.inner.write_str(chars.as_str())
+            }
+            _ => self.inner.write_str(s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalStyle {
+    /// "three point one four"
+    Point,
+    /// "three and fourteen hundredths"
+    Fraction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStyle {
+    /// "twenty-one"
+    Cardinal,
+    /// "twenty-first"
+    Ordinal,
+    /// Cheque style: "one hundred twenty-three dollars and 45/100"
+    Currency,
+    /// "nineteen eighty-four"
+    Year,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleSystem {
+    /// 10^9 is a billion.
+    Short,
+    /// 10^9 is a milliard and 10^12 a billion.
+    Long,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrencyUnit {
+    pub singular: String,
+    pub plural: String,
+}
+
+impl Default for CurrencyUnit {
+    fn default() -> Self {
+        Self {
+            singular: "dollar".to_string(),
+            plural: "dollars".to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ConverterConfig {
-    pub max_value: u64,
+    /// Largest magnitude accepted; negative numbers are checked by absolute value.
+    pub max_value: u128,
     pub use_and: bool,  
     pub capitalize_first: bool,
+    pub decimal_style: DecimalStyle,
+    pub style: OutputStyle,
+    pub scale: ScaleSystem,
+    pub currency: CurrencyUnit,
+    pub language: Language,
+    pub gender: Gender,
 }
 
 impl Default for ConverterConfig {
     fn default() -> Self {
         Self {
-            max_value: 999_999_999_999_999_999, 
+            max_value: 999_999_999_999_999_999,
             use_and: false,
             capitalize_first: false,
+            decimal_style: DecimalStyle::Point,
+            style: OutputStyle::Cardinal,
+            scale: ScaleSystem::Short,
+            currency: CurrencyUnit::default(),
+            language: Language::English,
+            gender: Gender::Neutral,
         }
     }
 }
 
 const SCALE_WORDS: &[&str] = &[
-    "", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion"
+    "", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion",
+    "sextillion", "septillion", "octillion", "nonillion", "decillion", "undecillion"
 ];
 
+const LONG_SCALE_WORDS: &[&str] = &[
+    "", "thousand", "million", "milliard", "billion", "billiard", "trillion",
+    "trilliard", "quadrillion", "quadrilliard", "quintillion", "quintilliard", "sextillion"
+];
+
+const FRACTION_DENOMINATORS: &[&str] = &["", "ten", "hundred"];
+
 const ONES: &[&str] = &[
     "", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
     "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen",
@@ -61,58 +193,382 @@ const TENS: &[&str] = &[
     "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"
 ];
 
-#[derive(Debug, Clone)]
-pub struct NumberConverter {
-    config: ConverterConfig,
+pub trait LanguageBackend: fmt::Debug + Sync {
+    fn zero(&self) -> &'static str;
+    fn minus(&self) -> &'static str;
+    fn decimal_point(&self) -> &'static str;
+    /// Words for a non-zero `num`, inflected for `config.gender` where the language requires it.
+    fn convert(&self, num: u128, config: &ConverterConfig) -> String;
+
+    /// Streams the same words as `convert` into `out`; the default goes through `convert`.
+    fn write_words(&self, num: u128, config: &ConverterConfig, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str(&self.convert(num, config))
+    }
 }
 
-impl NumberConverter {
-    pub fn new() -> Self {
-        Self::with_config(ConverterConfig::default())
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Spanish,
+    French,
+    German,
+}
+
+impl Language {
+    pub fn backend(self) -> &'static dyn LanguageBackend {
+        match self {
+            Self::English => &English,
+            Self::Spanish => &Spanish,
+            Self::French => &French,
+            Self::German => &German,
+        }
     }
+}
 
-    pub fn with_config(config: ConverterConfig) -> Self {
-        Self { config }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    /// The form used when counting: "uno", "eins".
+    Neutral,
+    Masculine,
+    Feminine,
+}
+
+#[derive(Debug)]
+pub struct English;
+
+impl English {
+    fn scale_words(config: &ConverterConfig) -> &'static [&'static str] {
+        match config.scale {
+            ScaleSystem::Short => SCALE_WORDS,
+            ScaleSystem::Long => LONG_SCALE_WORDS,
+        }
     }
 
-    pub fn builder() -> ConverterBuilder {
-        ConverterBuilder::new()
+    fn write_group_of_three(num: u64, use_and: bool, out: &mut dyn fmt::Write) -> fmt::Result {
+        if num >= 100 {
+            let hundreds = num / 100;
+            out.write_str(ONES[hundreds as usize])?;
+            out.write_str(" hundred")?;
+
+            let remainder = num % 100;
+            if remainder > 0 {
+                if use_and {
+                    out.write_str(" and ")?;
+                } else {
+                    out.write_char(' ')?;
+                }
+            }
+        }
+
+        Self::write_under_hundred(num % 100, out)
     }
 
-    fn convert_internal(&self, num: u64) -> Result<String> {
-        if num > self.config.max_value {
-            return Err(ConversionError::NumberTooLarge {
-                value: num,
-                max: self.config.max_value,
-            });
+    fn write_under_hundred(num: u64, out: &mut dyn fmt::Write) -> fmt::Result {
+        match num {
+            0 => Ok(()),
+            1..=19 => out.write_str(ONES[num as usize]),
+            _ => {
+                let tens = num / 10;
+                let ones = num % 10;
+
+                out.write_str(TENS[tens as usize])?;
+                if ones > 0 {
+                    out.write_char('-')?;
+                    out.write_str(ONES[ones as usize])?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn convert_under_hundred(num: u64) -> String {
+        let mut words = String::new();
+        Self::write_under_hundred(num, &mut words).expect("writing to a String cannot fail");
+        words
+    }
+}
+
+impl LanguageBackend for English {
+    fn zero(&self) -> &'static str {
+        "zero"
+    }
+
+    fn minus(&self) -> &'static str {
+        "minus"
+    }
+
+    fn decimal_point(&self) -> &'static str {
+        "point"
+    }
+
+    fn convert(&self, num: u128, config: &ConverterConfig) -> String {
+        let mut words = String::new();
+        self.write_words(num, config, &mut words).expect("writing to a String cannot fail");
+        words
+    }
+
+    fn write_words(&self, mut num: u128, config: &ConverterConfig, out: &mut dyn fmt::Write) -> fmt::Result {
+        let scale_words = Self::scale_words(config);
+
+        // u128::MAX has 39 digits, so at most 13 groups of three.
+        let mut groups = [0u64; 13];
+        let mut count = 0;
+        while num > 0 {
+            groups[count] = (num % 1000) as u64;
+            num /= 1000;
+            count += 1;
         }
 
-        if num == 0 {
-            return Ok("zero".to_string());
+        let mut first = true;
+        for scale_index in (0..count).rev() {
+            let group = groups[scale_index];
+            if group == 0 {
+                continue;
+            }
+
+            if !first {
+                out.write_char(' ')?;
+            }
+            first = false;
+
+            Self::write_group_of_three(group, config.use_and, out)?;
+            if scale_index > 0 {
+                out.write_char(' ')?;
+                out.write_str(scale_words[scale_index])?;
+            }
         }
+        Ok(())
+    }
+}
 
-        let result = self.convert_large_number(num);
+const ES_UNDER_THIRTY: &[&str] = &[
+    "", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+    "diez", "once", "doce", "trece", "catorce", "quince", "dieciséis", "diecisiete",
+    "dieciocho", "diecinueve", "veinte", "veintiuno", "veintidós", "veintitrés",
+    "veinticuatro", "veinticinco", "veintiséis", "veintisiete", "veintiocho", "veintinueve"
+];
 
-        if self.config.capitalize_first {
-            Ok(Self::capitalize_first_letter(&result))
+const ES_TENS: &[&str] = &[
+    "", "", "", "treinta", "cuarenta", "cincuenta", "sesenta", "setenta", "ochenta", "noventa"
+];
+
+const ES_HUNDREDS: &[&str] = &[
+    "", "ciento", "doscientos", "trescientos", "cuatrocientos", "quinientos",
+    "seiscientos", "setecientos", "ochocientos", "novecientos"
+];
+
+// Spanish uses the long scale: each name is a million times the previous one.
+const ES_SCALES: &[(&str, &str)] = &[
+    ("", ""), ("millón", "millones"), ("billón", "billones"), ("trillón", "trillones"),
+    ("cuatrillón", "cuatrillones"), ("quintillón", "quintillones"), ("sextillón", "sextillones")
+];
+
+#[derive(Debug)]
+pub struct Spanish;
+
+impl Spanish {
+    // "uno" shortens before masculine nouns ("un", "veintiún") and becomes "una" before feminine ones.
+    fn inflect(word: &str, gender: Gender) -> String {
+        match (word, gender) {
+            ("uno", Gender::Masculine) => "un".to_string(),
+            ("uno", Gender::Feminine) => "una".to_string(),
+            ("veintiuno", Gender::Masculine) => "veintiún".to_string(),
+            ("veintiuno", Gender::Feminine) => "veintiuna".to_string(),
+            _ => word.to_string(),
+        }
+    }
+
+    // Multipliers of "mil" and "millón" never use the counting form "uno".
+    fn multiplier_gender(gender: Gender) -> Gender {
+        match gender {
+            Gender::Feminine => Gender::Feminine,
+            _ => Gender::Masculine,
+        }
+    }
+
+    fn under_hundred(num: u64, gender: Gender) -> String {
+        match num {
+            0 => String::new(),
+            1..=29 => Self::inflect(ES_UNDER_THIRTY[num as usize], gender),
+            _ => {
+                let tens = ES_TENS[(num / 10) as usize];
+                match num % 10 {
+                    0 => tens.to_string(),
+                    ones => format!("{} y {}", tens, Self::inflect(ES_UNDER_THIRTY[ones as usize], gender)),
+                }
+            }
+        }
+    }
+
+    fn under_thousand(num: u64, gender: Gender) -> String {
+        if num == 100 {
+            return "cien".to_string();
+        }
+
+        let mut parts = Vec::new();
+        let hundreds = (num / 100) as usize;
+        if hundreds > 0 {
+            let word = ES_HUNDREDS[hundreds];
+            if gender == Gender::Feminine && hundreds > 1 {
+                parts.push(format!("{}as", &word[..word.len() - 2]));
+            } else {
+                parts.push(word.to_string());
+            }
+        }
+
+        let rest = num % 100;
+        if rest > 0 {
+            parts.push(Self::under_hundred(rest, gender));
+        }
+        parts.join(" ")
+    }
+
+    fn under_million(num: u64, gender: Gender) -> String {
+        let mut parts = Vec::new();
+
+        match num / 1000 {
+            0 => {}
+            1 => parts.push("mil".to_string()),
+            thousands => parts.push(format!("{} mil", Self::under_thousand(thousands, Self::multiplier_gender(gender)))),
+        }
+
+        let rest = num % 1000;
+        if rest > 0 {
+            parts.push(Self::under_thousand(rest, gender));
+        }
+        parts.join(" ")
+    }
+}
+
+impl LanguageBackend for Spanish {
+    fn zero(&self) -> &'static str {
+        "cero"
+    }
+
+    fn minus(&self) -> &'static str {
+        "menos"
+    }
+
+    fn decimal_point(&self) -> &'static str {
+        "coma"
+    }
+
+    fn convert(&self, mut num: u128, config: &ConverterConfig) -> String {
+        let mut parts = Vec::new();
+        let mut scale_index = 0;
+
+        while num > 0 {
+            let chunk = (num % 1_000_000) as u64;
+            if chunk > 0 {
+                let (singular, plural) = ES_SCALES[scale_index];
+                parts.push(match (scale_index, chunk) {
+                    (0, _) => Self::under_million(chunk, config.gender),
+                    (_, 1) => format!("un {}", singular),
+                    _ => format!("{} {}", Self::under_million(chunk, Gender::Masculine), plural),
+                });
+            }
+            num /= 1_000_000;
+            scale_index += 1;
+        }
+
+        parts.reverse();
+        parts.join(" ")
+    }
+}
+
+const FR_UNITS: &[&str] = &[
+    "", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+    "dix", "onze", "douze", "treize", "quatorze", "quinze", "seize"
+];
+
+const FR_TENS: &[&str] = &["", "dix", "vingt", "trente", "quarante", "cinquante", "soixante"];
+
+const FR_SCALES: &[&str] = &[
+    "", "mille", "million", "milliard", "billion", "billiard", "trillion",
+    "trilliard", "quadrillion", "quadrilliard", "quintillion", "quintilliard", "sextillion"
+];
+
+#[derive(Debug)]
+pub struct French;
+
+impl French {
+    fn unit(num: u64, gender: Gender) -> &'static str {
+        if num == 1 && gender == Gender::Feminine {
+            "une"
         } else {
-            Ok(result)
+            FR_UNITS[num as usize]
+        }
+    }
+
+    // 70-79 count on from "soixante" and 80-99 from "quatre-vingt" (vigesimal counting).
+    // `plural` is false before "mille", which keeps "vingt" and "cent" invariable.
+    fn under_hundred(num: u64, gender: Gender, plural: bool) -> String {
+        match num {
+            0 => String::new(),
+            1..=16 => Self::unit(num, gender).to_string(),
+            17..=19 => format!("dix-{}", Self::unit(num - 10, gender)),
+            20..=69 => {
+                let tens = FR_TENS[(num / 10) as usize];
+                match num % 10 {
+                    0 => tens.to_string(),
+                    1 => format!("{} et {}", tens, Self::unit(1, gender)),
+                    ones => format!("{}-{}", tens, Self::unit(ones, gender)),
+                }
+            }
+            71 => "soixante et onze".to_string(),
+            70..=79 => format!("soixante-{}", Self::under_hundred(num - 60, gender, plural)),
+            80 if plural => "quatre-vingts".to_string(),
+            80 => "quatre-vingt".to_string(),
+            _ => format!("quatre-vingt-{}", Self::under_hundred(num - 80, gender, plural)),
+        }
+    }
+
+    fn under_thousand(num: u64, gender: Gender, plural: bool) -> String {
+        let hundreds = num / 100;
+        let rest = num % 100;
+        let mut parts = Vec::new();
+
+        match hundreds {
+            0 => {}
+            1 => parts.push("cent".to_string()),
+            _ if rest == 0 && plural => parts.push(format!("{} cents", FR_UNITS[hundreds as usize])),
+            _ => parts.push(format!("{} cent", FR_UNITS[hundreds as usize])),
+        }
+
+        if rest > 0 {
+            parts.push(Self::under_hundred(rest, gender, plural));
         }
+        parts.join(" ")
+    }
+}
+
+impl LanguageBackend for French {
+    fn zero(&self) -> &'static str {
+        "zéro"
     }
 
-    fn convert_large_number(&self, mut num: u64) -> String {
+    fn minus(&self) -> &'static str {
+        "moins"
+    }
+
+    fn decimal_point(&self) -> &'static str {
+        "virgule"
+    }
+
+    fn convert(&self, mut num: u128, config: &ConverterConfig) -> String {
         let mut parts = Vec::new();
         let mut scale_index = 0;
 
         while num > 0 {
-            let group = num % 1000;
+            let group = (num % 1000) as u64;
             if group > 0 {
-                let group_words = self.convert_group_of_three(group);
-                if scale_index > 0 {
-                    parts.push(format!("{} {}", group_words, SCALE_WORDS[scale_index]));
-                } else {
-                    parts.push(group_words);
-                }
+                parts.push(match (scale_index, group) {
+                    (0, _) => Self::under_thousand(group, config.gender, true),
+                    (1, 1) => "mille".to_string(),
+                    (1, _) => format!("{} mille", Self::under_thousand(group, config.gender, false)),
+                    (_, 1) => format!("un {}", FR_SCALES[scale_index]),
+                    _ => format!("{} {}s", Self::under_thousand(group, Gender::Masculine, true), FR_SCALES[scale_index]),
+                });
             }
             num /= 1000;
             scale_index += 1;
@@ -121,47 +577,377 @@ impl NumberConverter {
         parts.reverse();
         parts.join(" ")
     }
+}
 
-    fn convert_group_of_three(&self, num: u64) -> String {
-        let mut result = String::new();
+const DE_UNITS: &[&str] = &[
+    "", "ein", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+    "zehn", "elf", "zwölf", "dreizehn", "vierzehn", "fünfzehn", "sechzehn",
+    "siebzehn", "achtzehn", "neunzehn"
+];
 
-        if num >= 100 {
-            let hundreds = num / 100;
-            result.push_str(ONES[hundreds as usize]);
-            result.push_str(" hundred");
+const DE_TENS: &[&str] = &[
+    "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig"
+];
 
-            let remainder = num % 100;
-            if remainder > 0 {
-                if self.config.use_and {
-                    result.push_str(" and ");
-                } else {
-                    result.push(' ');
+const DE_SCALES: &[(&str, &str)] = &[
+    ("", ""), ("", ""), ("Million", "Millionen"), ("Milliarde", "Milliarden"),
+    ("Billion", "Billionen"), ("Billiarde", "Billiarden"), ("Trillion", "Trillionen"),
+    ("Trilliarde", "Trilliarden"), ("Quadrillion", "Quadrillionen"), ("Quadrilliarde", "Quadrilliarden"),
+    ("Quintillion", "Quintillionen"), ("Quintilliarde", "Quintilliarden"), ("Sextillion", "Sextillionen")
+];
+
+#[derive(Debug)]
+pub struct German;
+
+impl German {
+    // Units come before tens and everything below a million is written as one word.
+    fn under_hundred(num: u64) -> String {
+        match num {
+            0 => String::new(),
+            1..=19 => DE_UNITS[num as usize].to_string(),
+            _ => {
+                let tens = DE_TENS[(num / 10) as usize];
+                match num % 10 {
+                    0 => tens.to_string(),
+                    ones => format!("{}und{}", DE_UNITS[ones as usize], tens),
                 }
             }
         }
+    }
 
-        let remainder = num % 100;
-        if remainder > 0 {
-            result.push_str(&self.convert_under_hundred(remainder));
+    fn under_thousand(num: u64) -> String {
+        let mut result = String::new();
+        if num >= 100 {
+            result.push_str(DE_UNITS[(num / 100) as usize]);
+            result.push_str("hundert");
         }
+        result.push_str(&Self::under_hundred(num % 100));
         result
     }
 
-    fn convert_under_hundred(&self, num: u64) -> String {
-        match num {
-            0 => String::new(),
-            1..=19 => ONES[num as usize].to_string(),
-            _ => {
-                let tens = num / 10;
-                let ones = num % 10;
+    // A trailing "ein" is "eins" when counting, "ein" or "eine" before a noun.
+    fn final_one_suffix(gender: Gender) -> &'static str {
+        match gender {
+            Gender::Neutral => "s",
+            Gender::Masculine => "",
+            Gender::Feminine => "e",
+        }
+    }
+}
 
-                if ones == 0 {
-                    TENS[tens as usize].to_string()
-                } else {
-                    format!("{}-{}", TENS[tens as usize], ONES[ones as usize])
+impl LanguageBackend for German {
+    fn zero(&self) -> &'static str {
+        "null"
+    }
+
+    fn minus(&self) -> &'static str {
+        "minus"
+    }
+
+    fn decimal_point(&self) -> &'static str {
+        "Komma"
+    }
+
+    fn convert(&self, num: u128, config: &ConverterConfig) -> String {
+        let mut parts = Vec::new();
+
+        let below_million = (num % 1_000_000) as u64;
+        if below_million > 0 {
+            let mut words = String::new();
+            if below_million >= 1000 {
+                words.push_str(&Self::under_thousand(below_million / 1000));
+                words.push_str("tausend");
+            }
+            words.push_str(&Self::under_thousand(below_million % 1000));
+            if below_million % 100 == 1 {
+                words.push_str(Self::final_one_suffix(config.gender));
+            }
+            parts.push(words);
+        }
+
+        let mut rest = num / 1_000_000;
+        let mut scale_index = 2;
+        while rest > 0 {
+            let group = (rest % 1000) as u64;
+            if group > 0 {
+                let (singular, plural) = DE_SCALES[scale_index];
+                parts.push(match group {
+                    1 => format!("eine {}", singular),
+                    // "Million" and friends are feminine: "einhunderteine Millionen".
+                    _ if group % 100 == 1 => format!("{}e {}", Self::under_thousand(group), plural),
+                    _ => format!("{} {}", Self::under_thousand(group), plural),
+                });
+            }
+            rest /= 1000;
+            scale_index += 1;
+        }
+
+        parts.reverse();
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NumberConverter {
+    config: ConverterConfig,
+}
+
+impl NumberConverter {
+    pub fn new() -> Self {
+        Self::with_config(ConverterConfig::default())
+    }
+
+    pub fn with_config(config: ConverterConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn builder() -> ConverterBuilder {
+        ConverterBuilder::new()
+    }
+
+    fn convert_internal(&self, negative: bool, num: u128) -> Result<String> {
+        self.check_max(num)?;
+        if self.config.style != OutputStyle::Cardinal {
+            self.require_english(&format!("{:?} style", self.config.style))?;
+        }
+
+        let mut result = match self.config.style {
+            OutputStyle::Cardinal => self.cardinal(num),
+            OutputStyle::Ordinal => Self::ordinalize(&self.cardinal(num)),
+            OutputStyle::Currency => self.currency(num, 0),
+            OutputStyle::Year => self.year(num),
+        };
+        if negative && num > 0 {
+            self.prepend_minus(&mut result);
+        }
+
+        Ok(self.finish(result))
+    }
+
+    fn write_internal(&self, negative: bool, num: u128, out: &mut dyn fmt::Write) -> Result<()> {
+        // Only cardinals stream; the other styles rework their last words and are built in full.
+        if self.config.style != OutputStyle::Cardinal {
+            let words = self.convert_internal(negative, num)?;
+            return out.write_str(&words).map_err(|_| formatter_error());
+        }
+        self.check_max(num)?;
+
+        let backend = self.backend();
+        let mut out = CapitalizeFirst {
+            inner: out,
+            pending: self.config.capitalize_first,
+        };
+        let mut write = || {
+            if negative && num > 0 {
+                out.write_str(backend.minus())?;
+                out.write_char(' ')?;
+            }
+            if num == 0 {
+                out.write_str(backend.zero())
+            } else {
+                backend.write_words(num, &self.config, &mut out)
+            }
+        };
+
+        write().map_err(|_| formatter_error())
+    }
+
+    fn backend(&self) -> &'static dyn LanguageBackend {
+        self.config.language.backend()
+    }
+
+    fn require_english(&self, feature: &str) -> Result<()> {
+        if self.config.language == Language::English {
+            Ok(())
+        } else {
+            Err(ConversionError::Unsupported {
+                language: self.config.language,
+                feature: feature.to_string(),
+            })
+        }
+    }
+
+    fn prepend_minus(&self, result: &mut String) {
+        result.insert_str(0, &format!("{} ", self.backend().minus()));
+    }
+
+    fn ordinalize(words: &str) -> String {
+        let split = words.rfind([' ', '-']).map_or(0, |i| i + 1);
+        let (head, last) = words.split_at(split);
+
+        let ordinal = match last {
+            "one" => "first".to_string(),
+            "two" => "second".to_string(),
+            "three" => "third".to_string(),
+            "five" => "fifth".to_string(),
+            "eight" => "eighth".to_string(),
+            "nine" => "ninth".to_string(),
+            "twelve" => "twelfth".to_string(),
+            w if w.ends_with('y') => format!("{}ieth", &w[..w.len() - 1]),
+            w => format!("{}th", w),
+        };
+
+        format!("{}{}", head, ordinal)
+    }
+
+    fn currency(&self, units: u128, cents: u8) -> String {
+        let unit = if units == 1 {
+            &self.config.currency.singular
+        } else {
+            &self.config.currency.plural
+        };
+        format!("{} {} and {:02}/100", self.cardinal(units), unit, cents)
+    }
+
+    // Years are read in pairs ("nineteen eighty-four") except when that sounds wrong,
+    // as for 2000-2009 ("two thousand five") or anything outside 1000-9999.
+    fn year(&self, num: u128) -> String {
+        if !(1000..=9999).contains(&num) || num % 1000 < 10 {
+            return self.cardinal(num);
+        }
+
+        let century = English::convert_under_hundred((num / 100) as u64);
+        match (num % 100) as u64 {
+            0 => format!("{} hundred", century),
+            rest @ 1..=9 => format!("{} oh {}", century, ONES[rest as usize]),
+            rest => format!("{} {}", century, English::convert_under_hundred(rest)),
+        }
+    }
+
+    fn check_max(&self, num: u128) -> Result<()> {
+        if num > self.config.max_value {
+            return Err(ConversionError::NumberTooLarge {
+                value: num,
+                max: self.config.max_value,
+            });
+        }
+        Ok(())
+    }
+
+    fn finish(&self, result: String) -> String {
+        if self.config.capitalize_first {
+            Self::capitalize_first_letter(&result)
+        } else {
+            result
+        }
+    }
+
+    fn cardinal(&self, num: u128) -> String {
+        if num == 0 {
+            self.backend().zero().to_string()
+        } else {
+            self.backend().convert(num, &self.config)
+        }
+    }
+
+    pub fn decimal_to_words(&self, input: &str) -> Result<String> {
+        let invalid = || ConversionError::InvalidInput(input.to_string());
+
+        let trimmed = input.trim();
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let (integer_digits, fraction_digits) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (unsigned, ""),
+        };
+
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if (integer_digits.is_empty() && fraction_digits.is_empty())
+            || !is_digits(integer_digits)
+            || !is_digits(fraction_digits)
+            || unsigned.ends_with('.')
+        {
+            return Err(invalid());
+        }
+
+        let integer = if integer_digits.is_empty() {
+            0
+        } else {
+            integer_digits.parse::<u128>().map_err(|_| invalid())?
+        };
+        self.check_max(integer)?;
+
+        if fraction_digits.is_empty() {
+            return self.convert_internal(negative, integer);
+        }
+
+        if self.config.style == OutputStyle::Currency {
+            self.require_english("Currency style")?;
+            if fraction_digits.len() > 2 {
+                return Err(invalid());
+            }
+
+            let cents = format!("{:0<2}", fraction_digits).parse::<u8>().map_err(|_| invalid())?;
+            let mut result = self.currency(integer, cents);
+            if negative && (integer > 0 || cents > 0) {
+                self.prepend_minus(&mut result);
+            }
+            return Ok(self.finish(result));
+        }
+
+        let mut result = match self.config.decimal_style {
+            DecimalStyle::Point => {
+                let mut words = self.cardinal(integer);
+                words.push(' ');
+                words.push_str(self.backend().decimal_point());
+                for digit in fraction_digits.bytes() {
+                    words.push(' ');
+                    words.push_str(&self.cardinal(u128::from(digit - b'0')));
                 }
+                words
             }
+            DecimalStyle::Fraction => {
+                self.require_english("Fraction decimal style")?;
+                let numerator = fraction_digits.parse::<u128>().map_err(|_| invalid())?;
+                let denominator = self
+                    .fraction_denominator(fraction_digits.len(), numerator != 1)
+                    .ok_or_else(invalid)?;
+
+                match (integer, numerator) {
+                    (_, 0) => self.cardinal(integer),
+                    (0, _) => format!("{} {}", self.cardinal(numerator), denominator),
+                    _ => format!(
+                        "{} and {} {}",
+                        self.cardinal(integer),
+                        self.cardinal(numerator),
+                        denominator
+                    ),
+                }
+            }
+        };
+
+        let is_zero = integer == 0 && fraction_digits.bytes().all(|b| b == b'0');
+        if negative && !is_zero {
+            self.prepend_minus(&mut result);
+        }
+
+        Ok(self.finish(result))
+    }
+
+    // "tenths", "hundredths", "thousandths", "ten-thousandths", ... for `digits` decimal places.
+    fn fraction_denominator(&self, digits: usize, plural: bool) -> Option<String> {
+        if digits == 0 {
+            return Some(String::new());
+        }
+
+        let prefix = FRACTION_DENOMINATORS[digits % 3];
+        let scale = match digits / 3 {
+            0 => "",
+            index => English::scale_words(&self.config).get(index)?,
+        };
+
+        let mut name = match (prefix.is_empty(), scale.is_empty()) {
+            (false, false) => format!("{}-{}th", prefix, scale),
+            (false, true) => format!("{}th", prefix),
+            _ => format!("{}th", scale),
+        };
+        if plural {
+            name.push('s');
         }
+        Some(name)
     }
 
     fn capitalize_first_letter(s: &str) -> String {
@@ -179,20 +965,55 @@ impl Default for NumberConverter {
     }
 }
 
-impl NumberToWords<u64> for NumberConverter {
-    fn to_words(&self, num: u64) -> Result<String> {
-        self.convert_internal(num)
-    }
+macro_rules! impl_unsigned_to_words {
+    ($($t:ty),*) => {$(
+        impl NumberToWords<$t> for NumberConverter {
+            fn to_words(&self, num: $t) -> Result<String> {
+                self.convert_internal(false, num as u128)
+            }
 
-    fn max_value(&self) -> u64 {
-        self.config.max_value
-    }
+            fn write_words(&self, num: $t, out: &mut dyn fmt::Write) -> Result<()> {
+                self.write_internal(false, num as u128, out)
+            }
+
+            fn max_value(&self) -> $t {
+                self.config.max_value.min(<$t>::MAX as u128) as $t
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed_to_words {
+    ($($t:ty),*) => {$(
+        impl NumberToWords<$t> for NumberConverter {
+            fn to_words(&self, num: $t) -> Result<String> {
+                self.convert_internal(num < 0, num.unsigned_abs() as u128)
+            }
+
+            fn write_words(&self, num: $t, out: &mut dyn fmt::Write) -> Result<()> {
+                self.write_internal(num < 0, num.unsigned_abs() as u128, out)
+            }
+
+            fn max_value(&self) -> $t {
+                self.config.max_value.min(<$t>::MAX as u128) as $t
+            }
+        }
+    )*};
 }
 
+impl_unsigned_to_words!(u8, u16, u32, u64, u128, usize);
+impl_signed_to_words!(i8, i16, i32, i64, i128, isize);
+
 pub struct ConverterBuilder {
     config: ConverterConfig,
 }
 
+impl Default for ConverterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ConverterBuilder {
     pub fn new() -> Self {
         Self {
@@ -200,7 +1021,7 @@ impl ConverterBuilder {
         }
     }
 
-    pub fn max_value(mut self, max: u64) -> Self {
+    pub fn max_value(mut self, max: u128) -> Self {
         self.config.max_value = max;
         self
     }
@@ -215,11 +1036,198 @@ impl ConverterBuilder {
         self
     }
 
+    pub fn decimal_style(mut self, style: DecimalStyle) -> Self {
+        self.config.decimal_style = style;
+        self
+    }
+
+    pub fn style(mut self, style: OutputStyle) -> Self {
+        self.config.style = style;
+        self
+    }
+
+    pub fn scale(mut self, scale: ScaleSystem) -> Self {
+        self.config.scale = scale;
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.config.language = language;
+        self
+    }
+
+    pub fn gender(mut self, gender: Gender) -> Self {
+        self.config.gender = gender;
+        self
+    }
+
+    pub fn currency(mut self, singular: &str, plural: &str) -> Self {
+        self.config.currency = CurrencyUnit {
+            singular: singular.to_string(),
+            plural: plural.to_string(),
+        };
+        self
+    }
+
     pub fn build(self) -> NumberConverter {
         NumberConverter::with_config(self.config)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+    And,
+    Article,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+/// Parses spelled-out English numbers back into integers, the inverse of [`NumberConverter`].
+#[derive(Debug, Clone, Default)]
+pub struct WordsParser;
+
+impl WordsParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Accepts short-scale cardinals with optional "and", hyphens and commas, plus forms such as
+    /// "twelve hundred" and "a million". Error positions are byte offsets into `input`.
+    pub fn parse(&self, input: &str) -> Result<u64> {
+        let tokens = Self::tokenize(input);
+        let error = |position: usize, message: String| ConversionError::ParseError { position, message };
+
+        match tokens.as_slice() {
+            [] => return Err(error(0, "expected a number".to_string())),
+            [token] if token.text.eq_ignore_ascii_case("zero") => return Ok(0),
+            _ => {}
+        }
+
+        let mut total: u128 = 0;
+        let mut group: u128 = 0;
+        let mut group_has_hundred = false;
+        let mut last_scale: Option<usize> = None;
+        let mut last: Option<TokenKind> = None;
+
+        for token in &tokens {
+            let word = token.text.to_ascii_lowercase();
+            let unexpected = || error(token.position, format!("unexpected '{}'", token.text));
+
+            let (kind, value) = Self::classify(&word)
+                .ok_or_else(|| error(token.position, format!("unknown word '{}'", token.text)))?;
+
+            use TokenKind::*;
+            let allowed = match kind {
+                Unit => matches!(last, None | Some(Tens | Hundred | Scale | And)),
+                Teen | Tens => matches!(last, None | Some(Hundred | Scale | And)),
+                Hundred => matches!(last, Some(Unit | Teen | Tens | Article)) && !group_has_hundred,
+                Scale => matches!(last, Some(Unit | Teen | Tens | Hundred | Article)),
+                And => matches!(last, Some(Hundred | Scale)),
+                Article => last.is_none(),
+            };
+            if !allowed {
+                return Err(unexpected());
+            }
+
+            match kind {
+                Unit | Teen | Tens => group += value as u128,
+                Article => group = 1,
+                Hundred => {
+                    group *= 100;
+                    group_has_hundred = true;
+                    // "twelve hundred" stands in for "one thousand two hundred", so it can't
+                    // follow "thousand", which already covered the thousands.
+                    if group > 999 && last_scale.is_some_and(|previous| previous <= 1) {
+                        return Err(unexpected());
+                    }
+                }
+                Scale => {
+                    // Each scale must be smaller than the last, and its amount must fit below it:
+                    // "two million fifteen hundred thousand" would spill into the millions.
+                    let overlaps = |previous: usize| value >= previous || group >= 1000u128.pow((previous - value) as u32);
+                    if last_scale.is_some_and(overlaps) {
+                        return Err(unexpected());
+                    }
+
+                    total = 10u128
+                        .checked_pow(3 * value as u32)
+                        .and_then(|scale| group.checked_mul(scale))
+                        .and_then(|amount| total.checked_add(amount))
+                        .filter(|&total| total <= u128::from(u64::MAX))
+                        .ok_or_else(|| error(token.position, "number is too large".to_string()))?;
+                    group = 0;
+                    group_has_hundred = false;
+                    last_scale = Some(value);
+                }
+                And => {}
+            }
+
+            last = Some(kind);
+        }
+
+        if matches!(last, Some(TokenKind::And | TokenKind::Article)) {
+            return Err(error(input.len(), "unexpected end of input".to_string()));
+        }
+
+        let value = total + group;
+        u64::try_from(value).map_err(|_| error(tokens[tokens.len() - 1].position, "number is too large".to_string()))
+    }
+
+    fn tokenize(input: &str) -> Vec<Token<'_>> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+
+        for (i, c) in input.char_indices() {
+            let separator = c.is_whitespace() || c == '-' || c == ',';
+            match (separator, start) {
+                (true, Some(s)) => {
+                    tokens.push(Token { text: &input[s..i], position: s });
+                    start = None;
+                }
+                (false, None) => start = Some(i),
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            tokens.push(Token { text: &input[s..], position: s });
+        }
+
+        tokens
+    }
+
+    // For scales the value is the index into `SCALE_WORDS`.
+    fn classify(word: &str) -> Option<(TokenKind, usize)> {
+        match word {
+            "and" => return Some((TokenKind::And, 0)),
+            "a" | "an" => return Some((TokenKind::Article, 1)),
+            "hundred" => return Some((TokenKind::Hundred, 100)),
+            _ => {}
+        }
+
+        if let Some(n) = ONES.iter().position(|&w| w == word).filter(|&n| n > 0) {
+            let kind = if n < 10 { TokenKind::Unit } else { TokenKind::Teen };
+            return Some((kind, n));
+        }
+        if let Some(n) = TENS.iter().position(|&w| w == word).filter(|&n| n > 1) {
+            return Some((TokenKind::Tens, n * 10));
+        }
+        SCALE_WORDS
+            .iter()
+            .position(|&w| w == word)
+            .filter(|&n| n > 0)
+            .map(|n| (TokenKind::Scale, n))
+    }
+}
+
 fn get_user_input<T>() -> Result<Option<T>>
 where
     T: FromStr,
@@ -288,6 +1296,290 @@ where
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberReplacement {
+    pub line: usize,
+    pub column: usize,
+    pub source: String,
+    pub result: Result<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchSummary {
+    pub lines: usize,
+    pub converted: usize,
+    pub errors: Vec<NumberReplacement>,
+}
+
+/// Rewrites the numbers found in free text as words, one line at a time so arbitrarily
+/// large inputs can be streamed.
+pub struct BatchProcessor {
+    converter: NumberConverter,
+    format: OutputFormat,
+}
+
+impl BatchProcessor {
+    pub fn new(converter: NumberConverter, format: OutputFormat) -> Self {
+        Self { converter, format }
+    }
+
+    /// Byte ranges of the standalone numbers in `text`: an optional sign, digits with optional
+    /// comma thousands separators, and an optional decimal part. Digits that are part of a
+    /// word ("mp3"), version or address ("1.2.3") are left alone.
+    pub fn find_numbers(text: &str) -> Vec<Range<usize>> {
+        let bytes = text.as_bytes();
+        let digit_at = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+        let digits_from = |mut i: usize| {
+            while digit_at(i) {
+                i += 1;
+            }
+            i
+        };
+        let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let skip_token = |mut i: usize| {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'.' | b',')) {
+                i += 1;
+            }
+            i
+        };
+
+        let mut spans = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let signed = bytes[i] == b'-' && digit_at(i + 1);
+            if !(signed || bytes[i].is_ascii_digit()) {
+                i += text[i..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            }
+            let prev = text[..i].chars().next_back();
+            if signed && (prev == Some('-') || is_word(prev)) {
+                i += 1;
+                continue;
+            }
+            if is_word(prev) {
+                i = skip_token(i);
+                continue;
+            }
+
+            let start = i;
+            let digits_start = if signed { i + 1 } else { i };
+            let mut end = digits_from(digits_start);
+
+            if end - digits_start <= 3 {
+                while bytes.get(end) == Some(&b',') && digits_from(end + 1) == end + 4 {
+                    end += 4;
+                }
+            }
+            if bytes.get(end) == Some(&b'.') && digit_at(end + 1) {
+                end = digits_from(end + 1);
+            }
+
+            let trailing_word = is_word(text[end..].chars().next());
+            let trailing_number = matches!(bytes.get(end), Some(b'.' | b',')) && digit_at(end + 1);
+            if trailing_word || trailing_number {
+                end = skip_token(end);
+            } else {
+                spans.push(start..end);
+            }
+            i = end;
+        }
+
+        spans
+    }
+
+    /// Returns the rewritten line plus every number found in it. Numbers that fail to
+    /// convert keep their original digits.
+    pub fn convert_line(&self, line_number: usize, line: &str) -> (String, Vec<NumberReplacement>) {
+        let mut output = String::with_capacity(line.len() * 2);
+        let mut replacements = Vec::new();
+        let mut last = 0;
+
+        for span in Self::find_numbers(line) {
+            let source = &line[span.clone()];
+            let digits: String = source.chars().filter(|&c| c != ',').collect();
+            let result = self.converter.decimal_to_words(&digits);
+
+            output.push_str(&line[last..span.start]);
+            output.push_str(result.as_deref().unwrap_or(source));
+            last = span.end;
+
+            replacements.push(NumberReplacement {
+                line: line_number,
+                column: line[..span.start].chars().count() + 1,
+                source: source.to_string(),
+                result,
+            });
+        }
+        output.push_str(&line[last..]);
+
+        (output, replacements)
+    }
+
+    pub fn process(&self, name: &str, mut reader: impl BufRead, mut writer: impl Write) -> Result<BatchSummary> {
+        let io_error = |e: io::Error| ConversionError::IoError(e.to_string());
+        let mut summary = BatchSummary::default();
+        let mut buffer = String::new();
+
+        loop {
+            buffer.clear();
+            if reader.read_line(&mut buffer).map_err(io_error)? == 0 {
+                break;
+            }
+            summary.lines += 1;
+
+            let content = buffer.trim_end_matches(['\n', '\r']);
+            let ending = &buffer[content.len()..];
+            let (converted, replacements) = self.convert_line(summary.lines, content);
+
+            match self.format {
+                OutputFormat::Text => write!(writer, "{}{}", converted, ending),
+                OutputFormat::Json => writeln!(writer, "{}", Self::json_line(name, summary.lines, &converted, &replacements)),
+            }
+            .map_err(io_error)?;
+
+            for replacement in replacements {
+                match replacement.result {
+                    Ok(_) => summary.converted += 1,
+                    Err(_) => summary.errors.push(replacement),
+                }
+            }
+        }
+
+        writer.flush().map_err(io_error)?;
+        Ok(summary)
+    }
+
+    // One JSON object per input line, so the output can be consumed as a stream.
+    fn json_line(name: &str, line: usize, text: &str, replacements: &[NumberReplacement]) -> String {
+        let numbers: Vec<String> = replacements
+            .iter()
+            .map(|r| {
+                let outcome = match &r.result {
+                    Ok(words) => format!("\"words\":{}", json_string(words)),
+                    Err(e) => format!("\"error\":{}", json_string(&e.to_string())),
+                };
+                format!("{{\"column\":{},\"input\":{},{}}}", r.column, json_string(&r.source), outcome)
+            })
+            .collect();
+
+        format!(
+            "{{\"source\":{},\"line\":{},\"text\":{},\"numbers\":[{}]}}",
+            json_string(name),
+            line,
+            json_string(text),
+            numbers.join(",")
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn run_batch(converter: NumberConverter, args: &[String]) -> Result<()> {
+    let mut format = OutputFormat::Text;
+    let mut inputs = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => format = OutputFormat::Json,
+            "--batch" => {}
+            flag if flag.starts_with("--") => {
+                return Err(ConversionError::InvalidInput(format!("unknown option {}", flag)));
+            }
+            path => inputs.push(path.to_string()),
+        }
+    }
+    if inputs.is_empty() {
+        inputs.push("-".to_string());
+    }
+
+    let processor = BatchProcessor::new(converter, format);
+    let stdout = io::stdout();
+    let mut failed = 0;
+
+    for input in &inputs {
+        let summary = if input == "-" {
+            processor.process("<stdin>", io::stdin().lock(), stdout.lock())?
+        } else {
+            let file = File::open(input).map_err(|e| ConversionError::IoError(format!("{}: {}", input, e)))?;
+            processor.process(input, BufReader::new(file), stdout.lock())?
+        };
+
+        if format == OutputFormat::Text {
+            for error in &summary.errors {
+                if let Err(e) = &error.result {
+                    eprintln!("{}:{}:{}: {}", input, error.line, error.column, e);
+                }
+            }
+        }
+        failed += summary.errors.len();
+    }
+
+    if failed > 0 {
+        return Err(ConversionError::InvalidInput(format!("{} number(s) could not be converted", failed)));
+    }
+    Ok(())
+}
+
+fn run_benchmark(converter: &NumberConverter) -> Result<()> {
+    const VALUES: u64 = 1_000_000;
+    let value = |i: u64| i.wrapping_mul(7_919_993) % converter.config.max_value.min(u64::MAX as u128) as u64;
+
+    println!("Converting {} values...", VALUES);
+
+    let start = Instant::now();
+    let mut bytes = 0;
+    for i in 0..VALUES {
+        bytes += std::hint::black_box(converter.to_words(value(i))?).len();
+    }
+    let allocating = start.elapsed();
+    println!("to_words:               {:>8.1?} ({} bytes)", allocating, bytes);
+
+    let start = Instant::now();
+    let mut buffer = String::with_capacity(256);
+    let mut bytes = 0;
+    for i in 0..VALUES {
+        buffer.clear();
+        converter.write_words(value(i), &mut buffer)?;
+        bytes += std::hint::black_box(&buffer).len();
+    }
+    let streaming = start.elapsed();
+    println!("write_words (String):   {:>8.1?} ({} bytes)", streaming, bytes);
+
+    let start = Instant::now();
+    let mut sink = io::BufWriter::new(io::sink());
+    for i in 0..VALUES {
+        converter.write_words_io(value(i), &mut sink)?;
+    }
+    println!("write_words_io (sink):  {:>8.1?}", start.elapsed());
+
+    println!(
+        "Speed-up over to_words: {:.2}x",
+        allocating.as_secs_f64() / streaming.as_secs_f64().max(f64::EPSILON)
+    );
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let converter = NumberConverter::builder()
         .max_value(999_999_999_999)
@@ -295,6 +1587,14 @@ fn main() -> Result<()> {
         .capitalize_first(false)
         .build();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--benchmark") {
+        return run_benchmark(&converter);
+    }
+    if !args.is_empty() {
+        return run_batch(converter, &args);
+    }
+
     let session = InteractiveSession::new(converter);
     session.run()
 }
@@ -390,4 +1690,364 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_negative_numbers() {
+        let converter = NumberConverter::new();
+
+        assert_eq!(converter.to_words(-42i64).unwrap(), "minus forty-two");
+        assert_eq!(converter.to_words(0i64).unwrap(), "zero");
+        assert_eq!(converter.to_words(-1_000_000i128).unwrap(), "minus one million");
+        assert_eq!(
+            NumberConverter::builder().capitalize_first(true).build().to_words(-7i64).unwrap(),
+            "Minus seven"
+        );
+    }
+
+    #[test]
+    fn test_extended_scales() {
+        let converter = NumberConverter::builder().max_value(u128::MAX).build();
+
+        assert_eq!(converter.to_words(10u128.pow(21)).unwrap(), "one sextillion");
+        assert_eq!(converter.to_words(10u128.pow(36)).unwrap(), "one undecillion");
+        assert!(converter.to_words(u128::MAX).unwrap().starts_with("three hundred forty undecillion two hundred eighty-two decillion"));
+        assert!(converter.to_words(i128::MIN).unwrap().starts_with("minus one hundred seventy undecillion"));
+        assert_eq!(converter.to_words(u64::MAX).unwrap(),
+                   "eighteen quintillion four hundred forty-six quadrillion seven hundred forty-four trillion seventy-three billion seven hundred nine million five hundred fifty-one thousand six hundred fifteen");
+    }
+
+    #[test]
+    fn test_signed_max_value_respects_limit() {
+        let converter = NumberConverter::builder().max_value(100).build();
+
+        assert!(matches!(
+            converter.to_words(-101i64),
+            Err(ConversionError::NumberTooLarge { value: 101, max: 100 })
+        ));
+        assert_eq!(NumberToWords::<i64>::max_value(&converter), 100);
+        assert_eq!(NumberToWords::<i64>::max_value(&NumberConverter::new()), 999_999_999_999_999_999);
+        assert_eq!(NumberToWords::<i64>::max_value(&NumberConverter::builder().max_value(u128::MAX).build()), i64::MAX);
+    }
+
+    #[test]
+    fn test_decimal_point_style() {
+        let converter = NumberConverter::new();
+
+        assert_eq!(converter.decimal_to_words("3.14").unwrap(), "three point one four");
+        assert_eq!(converter.decimal_to_words("-0.05").unwrap(), "minus zero point zero five");
+        assert_eq!(converter.decimal_to_words(".5").unwrap(), "zero point five");
+        assert_eq!(converter.decimal_to_words("12").unwrap(), "twelve");
+        assert_eq!(converter.decimal_to_words("-0.0").unwrap(), "zero point zero");
+        assert_eq!(converter.decimal_to_words("-0").unwrap(), "zero");
+    }
+
+    #[test]
+    fn test_decimal_fraction_style() {
+        let converter = NumberConverter::builder()
+            .decimal_style(DecimalStyle::Fraction)
+            .build();
+
+        assert_eq!(converter.decimal_to_words("3.14").unwrap(), "three and fourteen hundredths");
+        assert_eq!(converter.decimal_to_words("0.1").unwrap(), "one tenth");
+        assert_eq!(converter.decimal_to_words("-2.0005").unwrap(), "minus two and five ten-thousandths");
+        assert_eq!(converter.decimal_to_words("1.000001").unwrap(), "one and one millionth");
+        assert_eq!(converter.decimal_to_words("7.00").unwrap(), "seven");
+        assert_eq!(converter.decimal_to_words("-0.00").unwrap(), "zero");
+        assert!(matches!(
+            converter.decimal_to_words(&format!("1.{}", "9".repeat(40))),
+            Err(ConversionError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_decimals() {
+        let converter = NumberConverter::new();
+
+        for input in ["", "-", "1.2.3", "1e5", "3.", "abc", "--1"] {
+            assert!(
+                matches!(converter.decimal_to_words(input), Err(ConversionError::InvalidInput(_))),
+                "expected {:?} to be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_ordinal_style() {
+        let converter = NumberConverter::builder().style(OutputStyle::Ordinal).build();
+
+        assert_eq!(converter.to_words(1).unwrap(), "first");
+        assert_eq!(converter.to_words(12).unwrap(), "twelfth");
+        assert_eq!(converter.to_words(20).unwrap(), "twentieth");
+        assert_eq!(converter.to_words(21).unwrap(), "twenty-first");
+        assert_eq!(converter.to_words(103).unwrap(), "one hundred third");
+        assert_eq!(converter.to_words(1_000_000).unwrap(), "one millionth");
+    }
+
+    #[test]
+    fn test_currency_style() {
+        let converter = NumberConverter::builder()
+            .style(OutputStyle::Currency)
+            .capitalize_first(true)
+            .build();
+
+        assert_eq!(converter.decimal_to_words("123.45").unwrap(), "One hundred twenty-three dollars and 45/100");
+        assert_eq!(converter.decimal_to_words("1.5").unwrap(), "One dollar and 50/100");
+        assert_eq!(converter.to_words(40).unwrap(), "Forty dollars and 00/100");
+        assert!(converter.decimal_to_words("1.234").is_err());
+
+        let euros = NumberConverter::builder()
+            .style(OutputStyle::Currency)
+            .currency("euro", "euros")
+            .build();
+        assert_eq!(euros.decimal_to_words("-0.99").unwrap(), "minus zero euros and 99/100");
+    }
+
+    #[test]
+    fn test_year_style() {
+        let converter = NumberConverter::builder().style(OutputStyle::Year).build();
+
+        assert_eq!(converter.to_words(1984).unwrap(), "nineteen eighty-four");
+        assert_eq!(converter.to_words(1900).unwrap(), "nineteen hundred");
+        assert_eq!(converter.to_words(1905).unwrap(), "nineteen oh five");
+        assert_eq!(converter.to_words(2005).unwrap(), "two thousand five");
+        assert_eq!(converter.to_words(2024).unwrap(), "twenty twenty-four");
+        assert_eq!(converter.to_words(1066).unwrap(), "ten sixty-six");
+        assert_eq!(converter.to_words(800).unwrap(), "eight hundred");
+    }
+
+    #[test]
+    fn test_long_scale() {
+        let converter = NumberConverter::builder().scale(ScaleSystem::Long).max_value(u128::MAX).build();
+
+        assert_eq!(converter.to_words(1_000_000_000u64).unwrap(), "one milliard");
+        assert_eq!(converter.to_words(2_500_000_000_000u64).unwrap(), "two billion five hundred milliard");
+        assert_eq!(converter.to_words(10u128.pow(36)).unwrap(), "one sextillion");
+
+        let fractions = NumberConverter::builder()
+            .scale(ScaleSystem::Long)
+            .decimal_style(DecimalStyle::Fraction)
+            .build();
+        assert_eq!(fractions.decimal_to_words("0.000000001").unwrap(), "one milliardth");
+    }
+
+    #[test]
+    fn test_spanish() {
+        let converter = NumberConverter::builder().language(Language::Spanish).build();
+
+        assert_eq!(converter.to_words(0).unwrap(), "cero");
+        assert_eq!(converter.to_words(21).unwrap(), "veintiuno");
+        assert_eq!(converter.to_words(35).unwrap(), "treinta y cinco");
+        assert_eq!(converter.to_words(100).unwrap(), "cien");
+        assert_eq!(converter.to_words(115).unwrap(), "ciento quince");
+        assert_eq!(converter.to_words(1_000).unwrap(), "mil");
+        assert_eq!(converter.to_words(21_000).unwrap(), "veintiún mil");
+        assert_eq!(converter.to_words(1_000_000).unwrap(), "un millón");
+        assert_eq!(converter.to_words(2_500_000).unwrap(), "dos millones quinientos mil");
+        assert_eq!(converter.to_words(1_000_000_000).unwrap(), "mil millones");
+        assert_eq!(converter.to_words(-3).unwrap(), "menos tres");
+    }
+
+    #[test]
+    fn test_spanish_gender() {
+        let feminine = NumberConverter::builder()
+            .language(Language::Spanish)
+            .gender(Gender::Feminine)
+            .build();
+        assert_eq!(feminine.to_words(21).unwrap(), "veintiuna");
+        assert_eq!(feminine.to_words(201).unwrap(), "doscientas una");
+        assert_eq!(feminine.to_words(2_000_000).unwrap(), "dos millones");
+
+        let masculine = NumberConverter::builder()
+            .language(Language::Spanish)
+            .gender(Gender::Masculine)
+            .build();
+        assert_eq!(masculine.to_words(31).unwrap(), "treinta y un");
+    }
+
+    #[test]
+    fn test_french() {
+        let converter = NumberConverter::builder().language(Language::French).build();
+
+        assert_eq!(converter.to_words(21).unwrap(), "vingt et un");
+        assert_eq!(converter.to_words(70).unwrap(), "soixante-dix");
+        assert_eq!(converter.to_words(71).unwrap(), "soixante et onze");
+        assert_eq!(converter.to_words(77).unwrap(), "soixante-dix-sept");
+        assert_eq!(converter.to_words(80).unwrap(), "quatre-vingts");
+        assert_eq!(converter.to_words(81).unwrap(), "quatre-vingt-un");
+        assert_eq!(converter.to_words(99).unwrap(), "quatre-vingt-dix-neuf");
+        assert_eq!(converter.to_words(200).unwrap(), "deux cents");
+        assert_eq!(converter.to_words(201).unwrap(), "deux cent un");
+        assert_eq!(converter.to_words(1_000).unwrap(), "mille");
+        assert_eq!(converter.to_words(80_000).unwrap(), "quatre-vingt mille");
+        assert_eq!(converter.to_words(200_000_000).unwrap(), "deux cents millions");
+        assert_eq!(converter.to_words(1_000_000_000).unwrap(), "un milliard");
+
+        let feminine = NumberConverter::builder()
+            .language(Language::French)
+            .gender(Gender::Feminine)
+            .build();
+        assert_eq!(feminine.to_words(41).unwrap(), "quarante et une");
+    }
+
+    #[test]
+    fn test_german() {
+        let converter = NumberConverter::builder().language(Language::German).build();
+
+        assert_eq!(converter.to_words(1).unwrap(), "eins");
+        assert_eq!(converter.to_words(21).unwrap(), "einundzwanzig");
+        assert_eq!(converter.to_words(101).unwrap(), "einhunderteins");
+        assert_eq!(converter.to_words(1_234).unwrap(), "eintausendzweihundertvierunddreißig");
+        assert_eq!(converter.to_words(1_000_000).unwrap(), "eine Million");
+        assert_eq!(converter.to_words(3_000_021).unwrap(), "drei Millionen einundzwanzig");
+        assert_eq!(converter.to_words(2_000_000_000).unwrap(), "zwei Milliarden");
+
+        let feminine = NumberConverter::builder()
+            .language(Language::German)
+            .gender(Gender::Feminine)
+            .build();
+        assert_eq!(feminine.to_words(1).unwrap(), "eine");
+    }
+
+    #[test]
+    fn test_other_languages_decimals_and_unsupported_styles() {
+        let german = NumberConverter::builder().language(Language::German).build();
+        assert_eq!(german.decimal_to_words("-3.14").unwrap(), "minus drei Komma eins vier");
+
+        let french_years = NumberConverter::builder()
+            .language(Language::French)
+            .style(OutputStyle::Year)
+            .build();
+        assert!(matches!(
+            french_years.to_words(1984),
+            Err(ConversionError::Unsupported { language: Language::French, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_words() {
+        let parser = WordsParser::new();
+
+        assert_eq!(parser.parse("zero").unwrap(), 0);
+        assert_eq!(parser.parse("forty-two").unwrap(), 42);
+        assert_eq!(parser.parse("one thousand two hundred and thirty-four").unwrap(), 1_234);
+        assert_eq!(parser.parse("twelve hundred").unwrap(), 1_200);
+        assert_eq!(parser.parse("twenty-five hundred thousand").unwrap(), 2_500_000);
+        assert_eq!(parser.parse("a million").unwrap(), 1_000_000);
+        assert_eq!(parser.parse("A Hundred and One").unwrap(), 101);
+        assert_eq!(parser.parse("one million, two thousand and five").unwrap(), 1_002_005);
+        assert_eq!(parser.parse("one million twelve hundred").unwrap(), 1_001_200);
+    }
+
+    #[test]
+    fn test_parse_errors_report_positions() {
+        let parser = WordsParser::new();
+        let position = |input: &str| match parser.parse(input) {
+            Err(ConversionError::ParseError { position, .. }) => position,
+            other => panic!("expected a parse error for {:?}, got {:?}", input, other),
+        };
+
+        assert_eq!(position(""), 0);
+        assert_eq!(position("one hundred blah"), 12);
+        assert_eq!(position("twenty thirty"), 7);
+        assert_eq!(position("one thousand two million"), 17);
+        assert_eq!(position("five hundred and"), 16);
+        assert_eq!(position("one hundred hundred"), 12);
+        assert_eq!(position("and five"), 0);
+        assert_eq!(position("twenty quintillion"), 7);
+        assert_eq!(position("one thousand twelve hundred"), 20);
+        assert_eq!(position("two million fifteen hundred thousand"), 28);
+    }
+
+    #[test]
+    fn test_words_round_trip() {
+        let converter = NumberConverter::builder().max_value(u128::MAX).build();
+        let british = NumberConverter::builder().use_and(true).max_value(u128::MAX).build();
+        let parser = WordsParser::new();
+
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let samples = (0..20_000u64).chain((0..20_000).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state >> (state % 64)
+        }));
+
+        for value in samples.chain([1_001_200, u64::MAX]) {
+            for converter in [&converter, &british] {
+                let words = converter.to_words(value).unwrap();
+                assert_eq!(parser.parse(&words), Ok(value), "round trip failed for {:?}", words);
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_numbers_in_text() {
+        let spans = |text: &str| -> Vec<String> {
+            BatchProcessor::find_numbers(text).into_iter().map(|r| text[r].to_string()).collect()
+        };
+
+        assert_eq!(spans("We sold 1,234,567 units for $3.50 each."), ["1,234,567", "3.50"]);
+        assert_eq!(spans("Temperature: -5, range 10-20"), ["-5", "10", "20"]);
+        assert_eq!(spans("mp3 v1.2 version 1.2.3 and 192.168.0.1"), Vec::<String>::new());
+        assert_eq!(spans("1, 2 and 3; but not 1,23 or 12,345,67"), ["1", "2", "3"]);
+        assert_eq!(spans("café 12, €5 and ½ of 30°"), ["12", "5", "30"]);
+        assert_eq!(spans("naïve42 日本7 — “-8”"), ["-8"]);
+    }
+
+    #[test]
+    fn test_batch_text_and_json_output() {
+        let converter = NumberConverter::builder().max_value(1_000_000).build();
+        let input = "Order 42 shipped 1,500 items.\nToo many: 2,000,000\n";
+
+        let text = BatchProcessor::new(converter.clone(), OutputFormat::Text);
+        let mut output = Vec::new();
+        let summary = text.process("orders", input.as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Order forty-two shipped one thousand five hundred items.\nToo many: 2,000,000\n"
+        );
+        assert_eq!((summary.lines, summary.converted, summary.errors.len()), (2, 2, 1));
+        assert_eq!((summary.errors[0].line, summary.errors[0].column), (2, 11));
+
+        let json = BatchProcessor::new(converter, OutputFormat::Json);
+        let mut output = Vec::new();
+        json.process("orders", "Say \"7\"\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"source\":\"orders\",\"line\":1,\"text\":\"Say \\\"seven\\\"\",\"numbers\":[{\"column\":6,\"input\":\"7\",\"words\":\"seven\"}]}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_words_matches_to_words() {
+        let converters = [
+            NumberConverter::builder(),
+            NumberConverter::builder().use_and(true).capitalize_first(true),
+            NumberConverter::builder().scale(ScaleSystem::Long),
+            NumberConverter::builder().style(OutputStyle::Ordinal),
+            NumberConverter::builder().language(Language::German).capitalize_first(true),
+        ]
+        .map(|builder| builder.max_value(u128::MAX).build());
+        let values = [0, 7, -15, 101, 1_000_010, -987_654_321_012, i128::MAX, i128::MIN];
+
+        let mut buffer = String::new();
+        for converter in &converters {
+            for &value in &values {
+                buffer.clear();
+                converter.write_words(value, &mut buffer).unwrap();
+                assert_eq!(buffer, converter.to_words(value).unwrap());
+            }
+        }
+
+        let mut bytes = Vec::new();
+        NumberConverter::new().write_words_io(1_234u32, &mut bytes).unwrap();
+        assert_eq!(bytes, b"one thousand two hundred thirty-four");
+
+        let limited = NumberConverter::builder().max_value(10).build();
+        assert!(matches!(
+            limited.write_words(11u8, &mut buffer),
+            Err(ConversionError::NumberTooLarge { .. })
+        ));
+    }
 }
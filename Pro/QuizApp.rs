@@ -1,10 +1,20 @@
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::fs;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 enum QuizError {
     IoError(io::Error),
     InvalidInput(String),
+    InvalidBank { source: String, message: String },
+    InvalidHistory { line: usize, message: String },
+    InvalidResults { line: usize, message: String },
 }
 
 impl From<io::Error> for QuizError {
@@ -18,6 +28,9 @@ impl Display for QuizError {
         match self {
             QuizError::IoError(e) => write!(f, "IO Error: {}", e),
             QuizError::InvalidInput(msg) => write!(f, "Invalid Input: {}", msg),
+            QuizError::InvalidBank { source, message } => write!(f, "Invalid quiz bank {}: {}", source, message),
+            QuizError::InvalidHistory { line, message } => write!(f, "Invalid study history at line {}: {}", line, message),
+            QuizError::InvalidResults { line, message } => write!(f, "Invalid quiz results at line {}: {}", line, message),
         }
     }
 }
@@ -35,6 +48,7 @@ trait Gradeable {
 
 trait Answerable {
     fn check_answer(&self, input: &str) -> bool;
+    fn correct_answer(&self) -> String;
 }
 
 #[derive(Debug, Clone)]
@@ -62,14 +76,43 @@ impl Question {
             correct_option,
         }
     }
+
+    fn option_letter(index: usize) -> char {
+        (b'a' + index as u8) as char
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        validate_options(&self.text, &self.options)?;
+        let last = Self::option_letter(self.options.len() - 1);
+        if !('a'..=last).contains(&self.correct_option) {
+            return Err(format!("correct option '{}' is not between 'a' and '{}'", self.correct_option, last));
+        }
+        Ok(())
+    }
+}
+
+fn validate_options(text: &str, options: &[String]) -> std::result::Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("question text is empty".to_string());
+    }
+    if !(2..=26).contains(&options.len()) {
+        return Err(format!("needs between 2 and 26 options, found {}", options.len()));
+    }
+    if options.iter().any(|option| option.trim().is_empty()) {
+        return Err("options must not be empty".to_string());
+    }
+    for (i, option) in options.iter().enumerate() {
+        if options[..i].iter().any(|other| other.eq_ignore_ascii_case(option)) {
+            return Err(format!("option \"{}\" appears twice", option));
+        }
+    }
+    Ok(())
 }
 
 impl Displayable for Question {
     fn display(&self) -> String {
-        let mut output = format!("{}. {}\n", self.id, self.text);
-        for (i, option) in self.options.iter().enumerate() {
-            output.push_str(&format!("{}) {}\n", (b'a' + i as u8) as char, option));
-        }
+        let mut output = format!("{}\n", self.text);
+        display_options(&mut output, &self.options);
         output
     }
 }
@@ -83,171 +126,3321 @@ impl Answerable for Question {
                 .iter()
                 .any(|answer| normalized_input == answer.to_lowercase())
     }
+
+    fn correct_answer(&self) -> String {
+        let index = (self.correct_option as u8 - b'a') as usize;
+        format!("{}) {}", self.correct_option, self.options[index])
+    }
 }
 
-#[derive(Debug)]
-struct QuizSession<T: Answerable + Displayable> {
-    questions: Vec<T>,
-    score: usize,
-    answers: Vec<String>,
+// Picks typed as letters ("a, c", "ca") or as option text ("Paris, Rome"), in the order given.
+fn selected_options(input: &str, options: &[String]) -> Option<Vec<usize>> {
+    let mut selected = Vec::new();
+    for part in input.split(',') {
+        let part = part.trim();
+        if let Some(index) = options.iter().position(|option| option.eq_ignore_ascii_case(part)) {
+            selected.push(index);
+            continue;
+        }
+        for c in part.chars().filter(|c| !c.is_whitespace()) {
+            let index = (c.to_ascii_lowercase() as u8).checked_sub(b'a')? as usize;
+            if !c.is_ascii_alphabetic() || index >= options.len() {
+                return None;
+            }
+            selected.push(index);
+        }
+    }
+    Some(selected)
 }
 
-impl<T: Answerable + Displayable> QuizSession<T> {
-    fn new(questions: Vec<T>) -> Self {
-        let answers = Vec::with_capacity(questions.len());
+fn display_options(output: &mut String, options: &[String]) {
+    for (i, option) in options.iter().enumerate() {
+        output.push_str(&format!("{}) {}\n", Question::option_letter(i), option));
+    }
+}
+
+fn is_permutation(indices: &[usize], len: usize) -> bool {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    sorted == (0..len).collect::<Vec<_>>()
+}
+
+#[derive(Debug, Clone)]
+struct MultiSelectQuestion {
+    id: usize,
+    text: String,
+    options: Vec<String>,
+    correct_options: Vec<usize>,
+}
+
+impl MultiSelectQuestion {
+    fn new(
+        id: usize,
+        text: impl Into<String>,
+        options: impl IntoIterator<Item = impl Into<String>>,
+        correct_options: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        let mut correct_options: Vec<usize> = correct_options.into_iter().collect();
+        correct_options.sort_unstable();
+        correct_options.dedup();
         Self {
-            questions,
-            score: 0,
-            answers,
+            id,
+            text: text.into(),
+            options: options.into_iter().map(Into::into).collect(),
+            correct_options,
         }
     }
-    
-    fn execute(&mut self) -> Result<()> {
-        self.display_header();
-        
-        for index in 0..self.questions.len() {
-            self.process_question_by_index(index)?;
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        validate_options(&self.text, &self.options)?;
+        if self.correct_options.is_empty() {
+            return Err("at least one option must be correct".to_string());
         }
-        
-        self.display_results();
         Ok(())
     }
-    
-    fn display_header(&self) {
-        println!("=== Rust Quiz App ===");
-        println!("Answer the following questions:\n");
+}
+
+impl Displayable for MultiSelectQuestion {
+    fn display(&self) -> String {
+        let mut output = format!("{} (select all that apply, e.g. a, c)\n", self.text);
+        display_options(&mut output, &self.options);
+        output
     }
-    
-    fn process_question_by_index(&mut self, index: usize) -> Result<()> {
-        print!("{}", self.questions[index].display());
-        
-        let user_input = self.get_validated_input()?;
-        
-        if self.questions[index].check_answer(&user_input) {
-            println!("Correct! ✓\n");
-            self.score += 1;
-        } else {
-            println!("Wrong! The correct answer is {}. ✗\n", 
-                     self.get_correct_answer_display(index));
+}
+
+impl Answerable for MultiSelectQuestion {
+    fn check_answer(&self, input: &str) -> bool {
+        selected_options(input, &self.options).is_some_and(|mut selected| {
+            selected.sort_unstable();
+            selected.dedup();
+            selected == self.correct_options
+        })
+    }
+
+    fn correct_answer(&self) -> String {
+        let answers: Vec<String> = self.correct_options.iter()
+            .map(|&i| format!("{}) {}", Question::option_letter(i), self.options[i]))
+            .collect();
+        answers.join(", ")
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrueFalseQuestion {
+    id: usize,
+    text: String,
+    answer: bool,
+}
+
+impl TrueFalseQuestion {
+    fn new(id: usize, text: impl Into<String>, answer: bool) -> Self {
+        Self { id, text: text.into(), answer }
+    }
+
+    fn parse(input: &str) -> Option<bool> {
+        match input.trim().to_lowercase().as_str() {
+            "t" | "true" | "y" | "yes" => Some(true),
+            "f" | "false" | "n" | "no" => Some(false),
+            _ => None,
         }
-        
-        self.answers.push(user_input);
-        Ok(())
     }
-    
-    fn get_validated_input(&self) -> Result<String> {
-        print!("Your answer: ");
-        io::stdout().flush()?;
-        
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        
-        let trimmed = input.trim();
-        if trimmed.is_empty() {
-            return Err(QuizError::InvalidInput("Empty input not allowed".to_string()));
+}
+
+impl Displayable for TrueFalseQuestion {
+    fn display(&self) -> String {
+        format!("{} (true/false)\n", self.text)
+    }
+}
+
+impl Answerable for TrueFalseQuestion {
+    fn check_answer(&self, input: &str) -> bool {
+        Self::parse(input) == Some(self.answer)
+    }
+
+    fn correct_answer(&self) -> String {
+        self.answer.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NumericQuestion {
+    id: usize,
+    text: String,
+    answer: f64,
+    tolerance: f64,
+    unit: Option<String>,
+}
+
+impl NumericQuestion {
+    fn new(id: usize, text: impl Into<String>, answer: f64, tolerance: f64) -> Self {
+        Self {
+            id,
+            text: text.into(),
+            answer,
+            tolerance,
+            unit: None,
         }
-        
-        Ok(trimmed.to_string())
     }
-    
-    fn get_correct_answer_display(&self, index: usize) -> String {
-        match index {
-            0 => "Paris".to_string(),
-            1 => "8".to_string(),
-            2 => "Mercury".to_string(),
-            _ => "Unknown".to_string(),
+
+    fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        if !self.answer.is_finite() {
+            return Err("the answer must be a finite number".to_string());
         }
+        if !(self.tolerance >= 0.0 && self.tolerance.is_finite()) {
+            return Err("the tolerance must be a non-negative number".to_string());
+        }
+        Ok(())
     }
-    
-    fn display_results(&self) {
-        println!("=== Quiz Complete ===");
-        println!("Your score: {}/{}", self.score, self.questions.len());
-        
-        let percentage = self.grade();
-        println!("Percentage: {:.1}%", percentage);
-        println!("{}", self.performance_message());
+}
+
+impl Displayable for NumericQuestion {
+    fn display(&self) -> String {
+        match &self.unit {
+            Some(unit) => format!("{} (number, in {})\n", self.text, unit),
+            None => format!("{} (number)\n", self.text),
+        }
     }
 }
 
-impl<T: Answerable + Displayable> Gradeable for QuizSession<T> {
-    fn grade(&self) -> f64 {
-        if self.questions.is_empty() {
-            return 0.0;
+impl Answerable for NumericQuestion {
+    // The unit may be typed after the number.
+    fn check_answer(&self, input: &str) -> bool {
+        let input = input.trim();
+        let number = self.unit.as_deref()
+            .and_then(|unit| input.strip_suffix(unit))
+            .unwrap_or(input);
+        number.trim().parse::<f64>().is_ok_and(|value| (value - self.answer).abs() <= self.tolerance)
+    }
+
+    fn correct_answer(&self) -> String {
+        let mut answer = self.answer.to_string();
+        if self.tolerance > 0.0 {
+            answer.push_str(&format!(" (±{})", self.tolerance));
         }
-        (self.score as f64 / self.questions.len() as f64) * 100.0
+        if let Some(unit) = &self.unit {
+            answer.push_str(&format!(" {}", unit));
+        }
+        answer
     }
-    
-    fn performance_message(&self) -> &'static str {
-        match self.grade() {
-            p if p >= 80.0 => "Excellent work! 🎉",
-            p if p >= 60.0 => "Good job! 👍",
-            _ => "Keep practicing! 📚",
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
         }
+        previous = current;
     }
+
+    previous[b.len()]
 }
 
-struct QuizBuilder {
-    questions: Vec<Question>,
+#[derive(Debug, Clone)]
+struct FreeTextQuestion {
+    id: usize,
+    text: String,
+    answers: Vec<String>,
+    max_typos: Option<usize>,
 }
 
-impl QuizBuilder {
-    fn new() -> Self {
+impl FreeTextQuestion {
+    // The first answer is the one shown; the rest are accepted synonyms.
+    fn new(id: usize, text: impl Into<String>, answers: impl IntoIterator<Item = impl Into<String>>) -> Self {
         Self {
-            questions: Vec::new(),
+            id,
+            text: text.into(),
+            answers: answers.into_iter().map(Into::into).collect(),
+            max_typos: None,
         }
     }
-    
-    fn add_question(
-        mut self,
+
+    fn with_max_typos(mut self, max_typos: usize) -> Self {
+        self.max_typos = Some(max_typos);
+        self
+    }
+
+    // Case, punctuation and spacing never count as typos.
+    fn normalize(text: &str) -> String {
+        let cleaned: String = text.chars()
+            .flat_map(|c| if c.is_alphanumeric() { c.to_lowercase().collect() } else { vec![' '] })
+            .collect();
+        cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        if self.text.trim().is_empty() {
+            return Err("question text is empty".to_string());
+        }
+        if self.answers.iter().all(|answer| Self::normalize(answer).is_empty()) {
+            return Err("at least one answer is required".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Displayable for FreeTextQuestion {
+    fn display(&self) -> String {
+        format!("{}\n", self.text)
+    }
+}
+
+impl Answerable for FreeTextQuestion {
+    // Unless set, one typo is forgiven per five characters, so short answers must be exact.
+    fn check_answer(&self, input: &str) -> bool {
+        let input = Self::normalize(input);
+        self.answers.iter().map(|answer| Self::normalize(answer)).any(|answer| {
+            let allowed = self.max_typos.unwrap_or(answer.chars().count() / 5);
+            !answer.is_empty() && edit_distance(&input, &answer) <= allowed
+        })
+    }
+
+    fn correct_answer(&self) -> String {
+        self.answers.first().cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OrderingQuestion {
+    id: usize,
+    text: String,
+    items: Vec<String>,
+    correct_order: Vec<usize>,
+}
+
+impl OrderingQuestion {
+    // `items` are shown in the given order; `correct_order` lists their indices first to last.
+    fn new(
+        id: usize,
         text: impl Into<String>,
-        options: impl IntoIterator<Item = impl Into<String>>,
-        correct_answers: impl IntoIterator<Item = impl Into<String>>,
-        correct_option: char,
+        items: impl IntoIterator<Item = impl Into<String>>,
+        correct_order: impl IntoIterator<Item = usize>,
     ) -> Self {
-        let id = self.questions.len() + 1;
-        self.questions.push(Question::new(
+        Self {
             id,
-            text,
-            options,
-            correct_answers,
-            correct_option,
-        ));
-        self
+            text: text.into(),
+            items: items.into_iter().map(Into::into).collect(),
+            correct_order: correct_order.into_iter().collect(),
+        }
     }
-    
-    fn build(self) -> QuizSession<Question> {
-        QuizSession::new(self.questions)
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        validate_options(&self.text, &self.items)?;
+        if !is_permutation(&self.correct_order, self.items.len()) {
+            return Err("the answer must list every item exactly once".to_string());
+        }
+        Ok(())
     }
 }
 
-fn run_quiz() -> Result<()> {
-    let mut quiz = QuizBuilder::new()
-        .add_question(
-            "What is the capital of France?",
-            ["London", "Paris", "Berlin"],
-            ["Paris"],
-            'b',
-        )
-        .add_question(
-            "What is 5 + 3?",
-            ["7", "8", "9"],
-            ["8"],
-            'b',
-        )
-        .add_question(
-            "Which planet is closest to the Sun?",
-            ["Venus", "Mercury", "Earth"],
-            ["Mercury"],
-            'b',
-        )
-        .build();
-    
-    quiz.execute()
+impl Displayable for OrderingQuestion {
+    fn display(&self) -> String {
+        let mut output = format!("{} (put in order, e.g. c, a, b)\n", self.text);
+        display_options(&mut output, &self.items);
+        output
+    }
 }
 
-fn main() {
-    if let Err(e) = run_quiz() {
-        eprintln!("Quiz failed: {}", e);
-        std::process::exit(1);
+impl Answerable for OrderingQuestion {
+    fn check_answer(&self, input: &str) -> bool {
+        selected_options(input, &self.items).is_some_and(|order| order == self.correct_order)
+    }
+
+    fn correct_answer(&self) -> String {
+        let letters: Vec<String> = self.correct_order.iter().map(|&i| Question::option_letter(i).to_string()).collect();
+        let items: Vec<&str> = self.correct_order.iter().map(|&i| self.items[i].as_str()).collect();
+        format!("{} ({})", letters.join(", "), items.join(" → "))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MatchingQuestion {
+    id: usize,
+    text: String,
+    prompts: Vec<String>,
+    choices: Vec<String>,
+    /// `pairs[i]` is the index of the choice that belongs to prompt `i`.
+    pairs: Vec<usize>,
+}
+
+impl MatchingQuestion {
+    fn new(
+        id: usize,
+        text: impl Into<String>,
+        prompts: impl IntoIterator<Item = impl Into<String>>,
+        choices: impl IntoIterator<Item = impl Into<String>>,
+        pairs: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        Self {
+            id,
+            text: text.into(),
+            prompts: prompts.into_iter().map(Into::into).collect(),
+            choices: choices.into_iter().map(Into::into).collect(),
+            pairs: pairs.into_iter().collect(),
+        }
+    }
+
+    // "1b, 2a" pairs in any order, or one letter per prompt in prompt order ("b a").
+    fn parse(&self, input: &str) -> Option<Vec<usize>> {
+        if !input.chars().any(|c| c.is_ascii_digit()) {
+            return selected_options(input, &self.choices).filter(|pairs| pairs.len() == self.prompts.len());
+        }
+
+        let mut pairs = vec![None; self.prompts.len()];
+        for part in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()) {
+            let split = part.find(|c: char| !c.is_ascii_digit())?;
+            let prompt = part[..split].parse::<usize>().ok()?.checked_sub(1)?;
+            let choice = selected_options(part[split..].trim_start_matches(['-', '=', ')', ':']), &self.choices)?;
+            match (pairs.get_mut(prompt)?, choice.as_slice()) {
+                (slot @ None, &[choice]) => *slot = Some(choice),
+                _ => return None,
+            }
+        }
+        pairs.into_iter().collect()
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        validate_options(&self.text, &self.choices)?;
+        if self.prompts.is_empty() || self.prompts.iter().any(|prompt| prompt.trim().is_empty()) {
+            return Err("matching questions need non-empty items to match".to_string());
+        }
+        if self.pairs.len() != self.prompts.len() || self.pairs.iter().any(|&choice| choice >= self.choices.len()) {
+            return Err(format!("the answer must give one option for each of the {} items", self.prompts.len()));
+        }
+        Ok(())
+    }
+}
+
+impl Displayable for MatchingQuestion {
+    fn display(&self) -> String {
+        let mut output = format!("{} (match each number to a letter, e.g. 1b, 2a)\n", self.text);
+        for (i, prompt) in self.prompts.iter().enumerate() {
+            output.push_str(&format!("{}. {}\n", i + 1, prompt));
+        }
+        display_options(&mut output, &self.choices);
+        output
+    }
+}
+
+impl Answerable for MatchingQuestion {
+    fn check_answer(&self, input: &str) -> bool {
+        self.parse(input).is_some_and(|pairs| pairs == self.pairs)
+    }
+
+    fn correct_answer(&self) -> String {
+        let pairs: Vec<String> = self.pairs.iter()
+            .enumerate()
+            .map(|(i, &choice)| format!("{}{} ({} → {})", i + 1, Question::option_letter(choice), self.prompts[i], self.choices[choice]))
+            .collect();
+        pairs.join(", ")
+    }
+}
+
+trait QuizQuestion: Answerable + Displayable {}
+
+impl<T: Answerable + Displayable> QuizQuestion for T {}
+
+// Any kind of question, so one quiz can mix them.
+#[derive(Debug, Clone)]
+enum QuizItem {
+    Choice(Question),
+    MultiSelect(MultiSelectQuestion),
+    TrueFalse(TrueFalseQuestion),
+    Numeric(NumericQuestion),
+    FreeText(FreeTextQuestion),
+    Ordering(OrderingQuestion),
+    Matching(MatchingQuestion),
+}
+
+impl QuizItem {
+    fn id(&self) -> usize {
+        match self {
+            QuizItem::Choice(question) => question.id,
+            QuizItem::MultiSelect(question) => question.id,
+            QuizItem::TrueFalse(question) => question.id,
+            QuizItem::Numeric(question) => question.id,
+            QuizItem::FreeText(question) => question.id,
+            QuizItem::Ordering(question) => question.id,
+            QuizItem::Matching(question) => question.id,
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            QuizItem::Choice(question) => &question.text,
+            QuizItem::MultiSelect(question) => &question.text,
+            QuizItem::TrueFalse(question) => &question.text,
+            QuizItem::Numeric(question) => &question.text,
+            QuizItem::FreeText(question) => &question.text,
+            QuizItem::Ordering(question) => &question.text,
+            QuizItem::Matching(question) => &question.text,
+        }
+    }
+
+    fn question(&self) -> &dyn QuizQuestion {
+        match self {
+            QuizItem::Choice(question) => question,
+            QuizItem::MultiSelect(question) => question,
+            QuizItem::TrueFalse(question) => question,
+            QuizItem::Numeric(question) => question,
+            QuizItem::FreeText(question) => question,
+            QuizItem::Ordering(question) => question,
+            QuizItem::Matching(question) => question,
+        }
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        match self {
+            QuizItem::Choice(question) => question.validate(),
+            QuizItem::MultiSelect(question) => question.validate(),
+            QuizItem::TrueFalse(question) if question.text.trim().is_empty() => Err("question text is empty".to_string()),
+            QuizItem::TrueFalse(_) => Ok(()),
+            QuizItem::Numeric(question) => question.validate(),
+            QuizItem::FreeText(question) => question.validate(),
+            QuizItem::Ordering(question) => question.validate(),
+            QuizItem::Matching(question) => question.validate(),
+        }
+    }
+}
+
+impl Displayable for QuizItem {
+    fn display(&self) -> String {
+        self.question().display()
+    }
+}
+
+impl Answerable for QuizItem {
+    fn check_answer(&self, input: &str) -> bool {
+        self.question().check_answer(input)
+    }
+
+    fn correct_answer(&self) -> String {
+        self.question().correct_answer()
+    }
+}
+
+macro_rules! impl_from_question {
+    ($($variant:ident($question:ty)),* $(,)?) => {
+        $(
+            impl From<$question> for QuizItem {
+                fn from(question: $question) -> Self {
+                    QuizItem::$variant(question)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_question!(
+    Choice(Question),
+    MultiSelect(MultiSelectQuestion),
+    TrueFalse(TrueFalseQuestion),
+    Numeric(NumericQuestion),
+    FreeText(FreeTextQuestion),
+    Ordering(OrderingQuestion),
+    Matching(MatchingQuestion),
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+enum Difficulty {
+    Easy = 1,
+    #[default]
+    Medium = 2,
+    Hard = 3,
+}
+
+impl Difficulty {
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "easy" | "1" => Some(Difficulty::Easy),
+            "medium" | "2" => Some(Difficulty::Medium),
+            "hard" | "3" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    // The spelling written to results and exports, which `parse` reads back.
+    fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    fn harder(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Medium,
+            _ => Difficulty::Hard,
+        }
+    }
+
+    fn easier(self) -> Self {
+        match self {
+            Difficulty::Hard => Difficulty::Medium,
+            _ => Difficulty::Easy,
+        }
+    }
+}
+
+// SplitMix64: small and seedable, so a quiz can be replayed from its seed. Not for secrets.
+struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Modulo bias is negligible for the handful of items a quiz shuffles.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    fn permutation(&mut self, len: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..len).collect();
+        self.shuffle(&mut order);
+        order
+    }
+}
+
+// Puts old item `order[k]` at position `k` and returns where each old index moved to.
+fn permute<T: Clone>(items: &mut Vec<T>, order: &[usize]) -> Vec<usize> {
+    *items = order.iter().map(|&old| items[old].clone()).collect();
+    let mut moved = vec![0; order.len()];
+    for (new, &old) in order.iter().enumerate() {
+        moved[old] = new;
+    }
+    moved
+}
+
+impl QuizItem {
+    // Shuffles the lettered options and remaps the answer to match.
+    fn shuffle_options(&mut self, rng: &mut SeededRng) {
+        match self {
+            QuizItem::Choice(question) => {
+                let order = rng.permutation(question.options.len());
+                let moved = permute(&mut question.options, &order);
+                let correct = (question.correct_option as u8 - b'a') as usize;
+                question.correct_option = Question::option_letter(moved[correct]);
+            }
+            QuizItem::MultiSelect(question) => {
+                let order = rng.permutation(question.options.len());
+                let moved = permute(&mut question.options, &order);
+                question.correct_options = question.correct_options.iter().map(|&old| moved[old]).collect();
+                question.correct_options.sort_unstable();
+            }
+            QuizItem::Ordering(question) => {
+                let order = rng.permutation(question.items.len());
+                let moved = permute(&mut question.items, &order);
+                question.correct_order = question.correct_order.iter().map(|&old| moved[old]).collect();
+            }
+            QuizItem::Matching(question) => {
+                let order = rng.permutation(question.prompts.len());
+                permute(&mut question.prompts, &order);
+                permute(&mut question.pairs, &order);
+                let order = rng.permutation(question.choices.len());
+                let moved = permute(&mut question.choices, &order);
+                question.pairs = question.pairs.iter().map(|&old| moved[old]).collect();
+            }
+            QuizItem::TrueFalse(_) | QuizItem::Numeric(_) | QuizItem::FreeText(_) => {}
+        }
+    }
+}
+
+// Where "now" comes from, so tests can run timed quizzes without waiting.
+trait TimeProvider {
+    /// Time elapsed since some fixed starting point.
+    fn now(&self) -> Duration;
+}
+
+struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl TimeProvider for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+trait AnswerSource {
+    /// Waits up to `timeout` (forever if `None`) for the next line; `Ok(None)` means time ran out.
+    fn next_answer(&mut self, timeout: Option<Duration>) -> Result<Option<String>>;
+}
+
+// std can't read stdin with a timeout, so a reader thread forwards lines over a channel.
+// The thread starts on first use so sessions that never ask anything don't touch stdin.
+#[derive(Default)]
+struct StdinAnswers {
+    receiver: Option<mpsc::Receiver<io::Result<String>>>,
+    /// Set after a timeout: whatever was typed for the expired question must not answer the next.
+    discard_pending: bool,
+}
+
+impl AnswerSource for StdinAnswers {
+    fn next_answer(&mut self, timeout: Option<Duration>) -> Result<Option<String>> {
+        let receiver = self.receiver.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                for line in io::stdin().lines() {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+            receiver
+        });
+
+        if std::mem::take(&mut self.discard_pending) {
+            while receiver.try_recv().is_ok() {}
+        }
+
+        let line = match timeout {
+            None => receiver.recv().ok(),
+            Some(timeout) => match receiver.recv_timeout(timeout) {
+                Ok(line) => Some(line),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    self.discard_pending = true;
+                    return Ok(None);
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => None,
+            },
+        };
+
+        // End of input reads as an empty answer, as `read_line` would.
+        Ok(Some(line.transpose()?.unwrap_or_default()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct TimeLimits {
+    per_question: Option<Duration>,
+    whole_quiz: Option<Duration>,
+}
+
+// A correct answer loses up to half its value as it uses up its time budget.
+const SPEED_WEIGHT: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+struct AnswerRecord {
+    /// `None` when the question was skipped because time ran out.
+    input: Option<String>,
+    correct: bool,
+    elapsed: Duration,
+}
+
+struct QuizSession<T: Answerable + Displayable> {
+    questions: Vec<T>,
+    score: usize,
+    answers: Vec<AnswerRecord>,
+    limits: TimeLimits,
+    clock: Rc<dyn TimeProvider>,
+    input: Box<dyn AnswerSource>,
+    question_limit: Option<usize>,
+    /// One per question when difficulty is adaptive, otherwise empty.
+    difficulties: Vec<Difficulty>,
+}
+
+impl<T: Answerable + Displayable> QuizSession<T> {
+    fn new(questions: Vec<T>) -> Self {
+        Self::with_io(questions, Rc::new(SystemClock::new()), Box::new(StdinAnswers::default()))
+    }
+
+    fn with_io(questions: Vec<T>, clock: Rc<dyn TimeProvider>, input: Box<dyn AnswerSource>) -> Self {
+        let answers = Vec::with_capacity(questions.len());
+        Self {
+            questions,
+            score: 0,
+            answers,
+            limits: TimeLimits::default(),
+            clock,
+            input,
+            question_limit: None,
+            difficulties: Vec::new(),
+        }
+    }
+
+    fn with_time_limits(mut self, limits: TimeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    // Only the first `limit` questions are asked; the rest are a pool for adaptive picks.
+    fn with_question_limit(mut self, limit: usize) -> Self {
+        self.question_limit = Some(limit);
+        self
+    }
+
+    fn with_adaptive_difficulty(mut self, difficulties: Vec<Difficulty>) -> Self {
+        assert_eq!(difficulties.len(), self.questions.len(), "one difficulty per question");
+        self.difficulties = difficulties;
+        self
+    }
+
+    // Moves the unasked question nearest the target difficulty into `index`, keeping the
+    // current order among equally good picks.
+    fn bring_closest(&mut self, index: usize, target: Difficulty) {
+        let distance = |difficulty: Difficulty| (difficulty as i32 - target as i32).abs();
+        if let Some(best) = (index..self.questions.len()).min_by_key(|&i| (distance(self.difficulties[i]), i)) {
+            self.questions[index..=best].rotate_right(1);
+            self.difficulties[index..=best].rotate_right(1);
+        }
+    }
+    
+    fn execute(&mut self) -> Result<()> {
+        self.display_header();
+        
+        let total = self.question_limit.map_or(self.questions.len(), |limit| limit.min(self.questions.len()));
+        let adaptive = !self.difficulties.is_empty();
+        let mut target = Difficulty::Medium;
+        let started = self.clock.now();
+        for index in 0..total {
+            let elapsed = self.clock.now().saturating_sub(started);
+            let remaining = self.limits.whole_quiz.map(|limit| limit.saturating_sub(elapsed));
+            if remaining == Some(Duration::ZERO) {
+                println!("⏰ Time's up! {} question(s) skipped.\n", total - index);
+                self.answers.resize(total, AnswerRecord { input: None, correct: false, elapsed: Duration::ZERO });
+                break;
+            }
+            if adaptive {
+                self.bring_closest(index, target);
+            }
+            self.process_question_by_index(index, remaining)?;
+            if adaptive {
+                target = if self.answers[index].correct { target.harder() } else { target.easier() };
+            }
+        }
+        self.questions.truncate(total);
+        self.difficulties.truncate(total);
+        
+        self.display_results();
+        Ok(())
+    }
+    
+    fn display_header(&self) {
+        println!("=== Rust Quiz App ===");
+        println!("Answer the following questions:\n");
+        if let Some(limit) = self.limits.per_question {
+            println!("You have {}s per question.", limit.as_secs_f64());
+        }
+        if let Some(limit) = self.limits.whole_quiz {
+            println!("You have {}s for the whole quiz.", limit.as_secs_f64());
+        }
+    }
+    
+    fn process_question_by_index(&mut self, index: usize, remaining: Option<Duration>) -> Result<()> {
+        print!("{}. {}", index + 1, self.questions[index].display());
+
+        let timeout = match (self.limits.per_question, remaining) {
+            (Some(limit), Some(remaining)) => Some(limit.min(remaining)),
+            (limit, remaining) => limit.or(remaining),
+        };
+        let asked = self.clock.now();
+        let user_input = self.get_validated_input(timeout)?;
+        let elapsed = self.clock.now().saturating_sub(asked);
+        
+        let correct = user_input.as_ref().is_some_and(|input| self.questions[index].check_answer(input));
+        if correct {
+            println!("Correct! ✓\n");
+            self.score += 1;
+        } else if user_input.is_none() {
+            println!("⏰ Time's up! The correct answer is {}.\n",
+                     self.questions[index].correct_answer());
+        } else {
+            println!("Wrong! The correct answer is {}. ✗\n",
+                     self.questions[index].correct_answer());
+        }
+        
+        self.answers.push(AnswerRecord { input: user_input, correct, elapsed });
+        Ok(())
+    }
+    
+    fn get_validated_input(&mut self, timeout: Option<Duration>) -> Result<Option<String>> {
+        print!("Your answer: ");
+        io::stdout().flush()?;
+        
+        let Some(input) = self.input.next_answer(timeout)? else {
+            println!();
+            return Ok(None);
+        };
+        
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Err(QuizError::InvalidInput("Empty input not allowed".to_string()));
+        }
+        
+        Ok(Some(trimmed.to_string()))
+    }
+
+    // The time a question may take: its own limit, or an even share of the quiz limit.
+    fn time_budget(&self) -> Option<Duration> {
+        self.limits.per_question.or_else(|| {
+            let questions = u32::try_from(self.questions.len()).ok().filter(|&n| n > 0)?;
+            self.limits.whole_quiz.map(|limit| limit / questions)
+        })
+    }
+    
+    fn display_results(&self) {
+        println!("=== Quiz Complete ===");
+        println!("Your score: {}/{}", self.score, self.questions.len());
+        
+        let percentage = self.grade();
+        if self.time_budget().is_some() {
+            let skipped = self.answers.iter().filter(|record| record.input.is_none()).count();
+            let total: Duration = self.answers.iter().map(|record| record.elapsed).sum();
+            println!("Skipped: {} | Time taken: {:.1}s", skipped, total.as_secs_f64());
+            println!("Speed-weighted percentage: {:.1}%", percentage);
+        } else {
+            println!("Percentage: {:.1}%", percentage);
+        }
+        println!("{}", self.performance_message());
+    }
+}
+
+impl<T: Answerable + Displayable> Gradeable for QuizSession<T> {
+    // Untimed quizzes score one point per correct answer; timed ones reward quick answers.
+    fn grade(&self) -> f64 {
+        if self.questions.is_empty() {
+            return 0.0;
+        }
+        let Some(budget) = self.time_budget().filter(|budget| !budget.is_zero()) else {
+            return (self.score as f64 / self.questions.len() as f64) * 100.0;
+        };
+
+        let points: f64 = self.answers.iter()
+            .filter(|record| record.correct)
+            .map(|record| 1.0 - SPEED_WEIGHT * (record.elapsed.as_secs_f64() / budget.as_secs_f64()).min(1.0))
+            .sum();
+        (points / self.questions.len() as f64) * 100.0
+    }
+    
+    fn performance_message(&self) -> &'static str {
+        match self.grade() {
+            p if p >= 80.0 => "Excellent work! 🎉",
+            p if p >= 60.0 => "Good job! 👍",
+            _ => "Keep practicing! 📚",
+        }
+    }
+}
+
+struct QuizBuilder {
+    questions: Vec<QuizItem>,
+}
+
+impl QuizBuilder {
+    fn new() -> Self {
+        Self {
+            questions: Vec::new(),
+        }
+    }
+    
+    fn add_question(
+        mut self,
+        text: impl Into<String>,
+        options: impl IntoIterator<Item = impl Into<String>>,
+        correct_answers: impl IntoIterator<Item = impl Into<String>>,
+        correct_option: char,
+    ) -> Self {
+        let id = self.questions.len() + 1;
+        self.questions.push(Question::new(
+            id,
+            text,
+            options,
+            correct_answers,
+            correct_option,
+        ).into());
+        self
+    }
+
+    fn add_true_false(mut self, text: impl Into<String>, answer: bool) -> Self {
+        let id = self.questions.len() + 1;
+        self.questions.push(TrueFalseQuestion::new(id, text, answer).into());
+        self
+    }
+
+    fn add_numeric(mut self, text: impl Into<String>, answer: f64, tolerance: f64) -> Self {
+        let id = self.questions.len() + 1;
+        self.questions.push(NumericQuestion::new(id, text, answer, tolerance).into());
+        self
+    }
+    
+    fn build(self) -> QuizSession<QuizItem> {
+        QuizSession::new(self.questions)
+    }
+}
+
+const QUIZ_DIRECTORY: &str = "data/quizzes";
+
+// Parser errors are plain messages; `QuizBank::load` attaches the file they came from.
+type ParseResult<T> = std::result::Result<T, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BankFormat {
+    Json,
+    Toml,
+    Markdown,
+}
+
+impl BankFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(BankFormat::Json),
+            "toml" => Some(BankFormat::Toml),
+            "md" | "markdown" => Some(BankFormat::Markdown),
+            _ => None,
+        }
+    }
+}
+
+enum FieldValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+// A question as written in a bank file, before it has been checked.
+struct RawQuestion {
+    location: String,
+    id: Option<String>,
+    kind: Option<String>,
+    text: Option<String>,
+    options: Vec<String>,
+    matches: Vec<String>,
+    answers: Vec<String>,
+    accept: Vec<String>,
+    tolerance: Option<String>,
+    unit: Option<String>,
+    max_typos: Option<String>,
+    tags: Vec<String>,
+    difficulty: Option<String>,
+}
+
+impl RawQuestion {
+    fn new(location: String) -> Self {
+        Self {
+            location,
+            id: None,
+            kind: None,
+            text: None,
+            options: Vec::new(),
+            matches: Vec::new(),
+            answers: Vec::new(),
+            accept: Vec::new(),
+            tolerance: None,
+            unit: None,
+            max_typos: None,
+            tags: Vec::new(),
+            difficulty: None,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: FieldValue) -> ParseResult<()> {
+        match (key, value) {
+            ("id", FieldValue::Text(id)) => self.id = Some(id),
+            ("type", FieldValue::Text(kind)) => self.kind = Some(kind),
+            ("text", FieldValue::Text(text)) => self.text = Some(text),
+            ("options", FieldValue::List(options)) => self.options = options,
+            ("matches", FieldValue::List(matches)) => self.matches = matches,
+            ("answer", FieldValue::Text(answer)) => self.answers = vec![answer],
+            ("answer", FieldValue::List(answers)) => self.answers = answers,
+            ("accept", FieldValue::List(accept)) => self.accept = accept,
+            ("accept", FieldValue::Text(accept)) => self.accept = vec![accept],
+            ("tolerance", FieldValue::Text(tolerance)) => self.tolerance = Some(tolerance),
+            ("unit", FieldValue::Text(unit)) => self.unit = Some(unit),
+            ("max_typos", FieldValue::Text(max_typos)) => self.max_typos = Some(max_typos),
+            ("tags", FieldValue::List(tags)) => self.tags = tags,
+            ("tags", FieldValue::Text(tag)) => self.tags = vec![tag],
+            ("difficulty", FieldValue::Text(difficulty)) => self.difficulty = Some(difficulty),
+            ("id" | "text" | "type" | "tolerance" | "unit" | "max_typos" | "difficulty", _) => {
+                return Err(format!("{}: \"{}\" must be a string", self.location, key));
+            }
+            ("options" | "matches", _) => {
+                return Err(format!("{}: \"{}\" must be a list of strings", self.location, key));
+            }
+            (key, _) => return Err(format!("{}: unknown field \"{}\"", self.location, key)),
+        }
+        Ok(())
+    }
+
+    // Each answer is an option letter or the option's text; "c, a, b" lists several at once.
+    fn answer_indices(answers: &[String], options: &[String]) -> ParseResult<Vec<usize>> {
+        answers.iter()
+            .flat_map(|answer| answer.split(','))
+            .map(|answer| {
+                let answer = answer.trim();
+                let letter = Some(answer.to_lowercase())
+                    .filter(|answer| answer.len() == 1)
+                    .and_then(|answer| answer.chars().next())
+                    .filter(char::is_ascii_lowercase)
+                    .map(|letter| (letter as u8 - b'a') as usize)
+                    .filter(|&index| index < options.len());
+                letter
+                    .or_else(|| options.iter().position(|option| option.eq_ignore_ascii_case(answer)))
+                    .ok_or_else(|| format!("answer \"{}\" is not one of the options", answer))
+            })
+            .collect()
+    }
+
+    // Study history and result stats are keyed on this id, so it has to survive edits to the
+    // bank. An explicit `id` does; otherwise it is derived from the text (FNV-1a), so adding,
+    // removing or reordering other questions leaves it alone but rewording starts afresh.
+    fn id(&self) -> ParseResult<usize> {
+        match &self.id {
+            Some(id) => id.trim().parse().ok().filter(|&id| id > 0)
+                .ok_or_else(|| format!("{}: id must be a positive whole number", self.location)),
+            None => {
+                let text = self.text.as_deref().unwrap_or_default().trim();
+                let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+                    (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+                });
+                // 53 bits, so the id survives a round trip through a JSON (f64) number.
+                Ok((hash >> 11) as usize)
+            }
+        }
+    }
+
+    fn meta(&self) -> ParseResult<QuestionMeta> {
+        let difficulty = match &self.difficulty {
+            Some(text) => Difficulty::parse(text)
+                .ok_or_else(|| format!("{}: difficulty must be easy, medium or hard", self.location))?,
+            None => Difficulty::default(),
+        };
+        Ok(QuestionMeta { tags: self.tags.clone(), difficulty })
+    }
+
+    fn parse_number<T: std::str::FromStr>(field: &str, value: Option<String>) -> ParseResult<Option<T>> {
+        value.map(|value| value.trim().parse().map_err(|_| format!("\"{}\" is not a valid {}", value, field)))
+            .transpose()
+    }
+
+    // `type` defaults to "choice". For choice questions the answer may also be a letter past
+    // the last option, which validation then reports, and the correct option's text is always
+    // accepted as a typed answer.
+    fn into_item(self, id: usize) -> ParseResult<QuizItem> {
+        let RawQuestion { location, kind, text, options, matches, answers, accept, tolerance, unit, max_typos, .. } = self;
+        let at = |message: String| format!("{}: {}", location, message);
+        let kind = kind.unwrap_or_else(|| "choice".to_string());
+
+        let text = text.ok_or_else(|| at("missing question text".to_string()))?;
+        if answers.is_empty() {
+            return Err(at("missing answer".to_string()));
+        }
+        let unused = [
+            ("options", !options.is_empty() && matches!(kind.as_str(), "true_false" | "numeric" | "text")),
+            ("matches", !matches.is_empty() && kind != "matching"),
+            ("accept", !accept.is_empty() && !matches!(kind.as_str(), "choice" | "text")),
+            ("tolerance", tolerance.is_some() && kind != "numeric"),
+            ("unit", unit.is_some() && kind != "numeric"),
+            ("max_typos", max_typos.is_some() && kind != "text"),
+        ];
+        if let Some((field, _)) = unused.iter().find(|(_, unused)| *unused) {
+            return Err(at(format!("\"{}\" is not used by {} questions", field, kind)));
+        }
+
+        let item: QuizItem = match kind.as_str() {
+            "choice" => {
+                let [answer] = answers.as_slice() else {
+                    return Err(at("choice questions take a single answer; use type \"multi\" for more".to_string()));
+                };
+                let letter = Some(answer.trim().to_lowercase())
+                    .filter(|answer| answer.len() == 1)
+                    .and_then(|answer| answer.chars().next())
+                    .filter(|letter| letter.is_ascii_lowercase());
+                let index = match letter {
+                    Some(letter) => (letter as u8 - b'a') as usize,
+                    None => options.iter()
+                        .position(|option| option.eq_ignore_ascii_case(answer.trim()))
+                        .ok_or_else(|| at(format!("answer \"{}\" is not one of the options", answer)))?,
+                };
+
+                let mut correct_answers: Vec<String> = options.get(index).cloned().into_iter().collect();
+                correct_answers.extend(accept);
+                Question::new(id, text, options, correct_answers, Question::option_letter(index)).into()
+            }
+            "multi" => {
+                let correct = Self::answer_indices(&answers, &options).map_err(at)?;
+                MultiSelectQuestion::new(id, text, options, correct).into()
+            }
+            "true_false" => {
+                let answer = match answers.as_slice() {
+                    [answer] => TrueFalseQuestion::parse(answer),
+                    _ => None,
+                };
+                let answer = answer.ok_or_else(|| at("the answer must be true or false".to_string()))?;
+                TrueFalseQuestion::new(id, text, answer).into()
+            }
+            "numeric" => {
+                let answer = match answers.as_slice() {
+                    [answer] => Self::parse_number("number", Some(answer.clone())).map_err(at)?,
+                    _ => None,
+                };
+                let answer = answer.ok_or_else(|| at("numeric questions take a single answer".to_string()))?;
+                let tolerance = Self::parse_number("tolerance", tolerance).map_err(at)?.unwrap_or(0.0);
+                let question = NumericQuestion::new(id, text, answer, tolerance);
+                match unit {
+                    Some(unit) => question.with_unit(unit).into(),
+                    None => question.into(),
+                }
+            }
+            "text" => {
+                let question = FreeTextQuestion::new(id, text, answers.into_iter().chain(accept));
+                match Self::parse_number("max_typos", max_typos).map_err(at)? {
+                    Some(max_typos) => question.with_max_typos(max_typos).into(),
+                    None => question.into(),
+                }
+            }
+            "ordering" => {
+                let order = Self::answer_indices(&answers, &options).map_err(at)?;
+                OrderingQuestion::new(id, text, options, order).into()
+            }
+            "matching" => {
+                let pairs = Self::answer_indices(&answers, &matches).map_err(at)?;
+                MatchingQuestion::new(id, text, options, matches, pairs).into()
+            }
+            kind => return Err(at(format!("unknown question type \"{}\"", kind))),
+        };
+
+        item.validate().map_err(at)?;
+        Ok(item)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "a boolean",
+            JsonValue::Number(_) => "a number",
+            JsonValue::String(_) => "a string",
+            JsonValue::Array(_) => "an array",
+            JsonValue::Object(_) => "an object",
+        }
+    }
+
+    fn into_field(self) -> Option<FieldValue> {
+        match self {
+            JsonValue::String(text) => Some(FieldValue::Text(text)),
+            JsonValue::Number(number) => Some(FieldValue::Text(number.to_string())),
+            JsonValue::Bool(value) => Some(FieldValue::Text(value.to_string())),
+            JsonValue::Array(items) => items.into_iter()
+                .map(|item| match item {
+                    JsonValue::String(text) => Some(text),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(FieldValue::List),
+            _ => None,
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse(text: &'a str) -> ParseResult<JsonValue> {
+        let mut parser = Self { text, pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < text.len() {
+            return Err(parser.error("unexpected text after the document"));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, message: &str) -> String {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        format!("line {}: {}", line, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> ParseResult<()> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    fn value(&mut self) -> ParseResult<JsonValue> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(JsonValue::String),
+            Some('t') => self.literal("true", JsonValue::Bool(true)),
+            Some('f') => self.literal("false", JsonValue::Bool(false)),
+            Some('n') => self.literal("null", JsonValue::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: JsonValue) -> ParseResult<JsonValue> {
+        if self.text[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn number(&mut self) -> ParseResult<JsonValue> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(c)) {
+            self.pos += 1;
+        }
+        self.text[start..self.pos].parse()
+            .map(JsonValue::Number)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    fn hex_escape(&mut self) -> ParseResult<u32> {
+        let digits = self.text.get(self.pos..self.pos + 4).ok_or_else(|| self.error("truncated \\u escape"))?;
+        let code = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn string(&mut self) -> ParseResult<String> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error("unterminated string")),
+                Some('"') => return Ok(result),
+                Some('\\') => {
+                    let escaped = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let mut code = self.hex_escape()?;
+                            // Characters outside the BMP arrive as a surrogate pair.
+                            if (0xD800..0xDC00).contains(&code) && self.text[self.pos..].starts_with("\\u") {
+                                self.pos += 2;
+                                let low = self.hex_escape()?;
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+                            }
+                            char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))?
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    result.push(escaped);
+                }
+                Some(c) if c.is_control() => return Err(self.error("control character in string")),
+                Some(c) => result.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> ParseResult<JsonValue> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(JsonValue::Array(items)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> ParseResult<JsonValue> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => {}
+                Some('}') => return Ok(JsonValue::Object(members)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+// {"title": "...", "questions": [{"text": "...", "options": [...], "answer": "b", "accept": [...]}]}
+fn parse_json_bank(contents: &str) -> ParseResult<(Option<String>, Vec<RawQuestion>)> {
+    let JsonValue::Object(members) = JsonParser::parse(contents)? else {
+        return Err("the document must be a JSON object".to_string());
+    };
+
+    let mut title = None;
+    let mut questions = Vec::new();
+    for (key, value) in members {
+        match (key.as_str(), value) {
+            ("title", JsonValue::String(text)) => title = Some(text),
+            ("questions", JsonValue::Array(items)) => {
+                for (i, item) in items.into_iter().enumerate() {
+                    let mut question = RawQuestion::new(format!("question {}", i + 1));
+                    let JsonValue::Object(fields) = item else {
+                        return Err(format!("question {}: expected an object, found {}", i + 1, item.type_name()));
+                    };
+                    for (key, value) in fields {
+                        let found = value.type_name();
+                        let value = value.into_field()
+                            .ok_or_else(|| format!("question {}: \"{}\" cannot be {}", i + 1, key, found))?;
+                        question.set(&key, value)?;
+                    }
+                    questions.push(question);
+                }
+            }
+            (key @ ("title" | "questions"), value) => {
+                return Err(format!("\"{}\" cannot be {}", key, value.type_name()));
+            }
+            (key, _) => return Err(format!("unknown field \"{}\"", key)),
+        }
+    }
+    Ok((title, questions))
+}
+
+#[derive(Debug, PartialEq)]
+enum TomlToken {
+    Str(String),
+    Open,
+    Close,
+    Comma,
+}
+
+// Just enough TOML for question banks: strings, arrays of strings and `#` comments. Bare
+// numbers and booleans are kept as their text.
+fn toml_tokens(text: &str) -> ParseResult<Vec<TomlToken>> {
+    let bare = |c: char| c.is_ascii_alphanumeric() || "+-._".contains(c);
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => break,
+            '[' => tokens.push(TomlToken::Open),
+            ']' => tokens.push(TomlToken::Close),
+            ',' => tokens.push(TomlToken::Comma),
+            '\'' => {
+                let literal: String = chars.by_ref().take_while(|&c| c != '\'').collect();
+                tokens.push(TomlToken::Str(literal));
+            }
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err("unterminated string".to_string()),
+                        Some('"') => break,
+                        Some('\\') => value.push(match chars.next() {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some('"') => '"',
+                            Some('\\') => '\\',
+                            _ => return Err("invalid escape in string".to_string()),
+                        }),
+                        Some(c) => value.push(c),
+                    }
+                }
+                tokens.push(TomlToken::Str(value));
+            }
+            c if c.is_whitespace() => {}
+            c if bare(c) => {
+                let mut value = c.to_string();
+                while let Some(c) = chars.next_if(|&c| bare(c)) {
+                    value.push(c);
+                }
+                tokens.push(TomlToken::Str(value));
+            }
+            c => return Err(format!("unexpected '{}'", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn toml_value(tokens: Vec<TomlToken>) -> ParseResult<FieldValue> {
+    let mut tokens = tokens.into_iter();
+    match tokens.next() {
+        Some(TomlToken::Str(text)) if tokens.len() == 0 => Ok(FieldValue::Text(text)),
+        Some(TomlToken::Open) => {
+            let mut items = Vec::new();
+            loop {
+                match tokens.next() {
+                    Some(TomlToken::Close) => break,
+                    Some(TomlToken::Str(item)) => {
+                        items.push(item);
+                        match tokens.next() {
+                            Some(TomlToken::Comma) => {}
+                            Some(TomlToken::Close) => break,
+                            _ => return Err("expected ',' or ']' in array".to_string()),
+                        }
+                    }
+                    _ => return Err("arrays may only contain strings".to_string()),
+                }
+            }
+            if tokens.len() > 0 {
+                return Err("unexpected text after array".to_string());
+            }
+            Ok(FieldValue::List(items))
+        }
+        _ => Err("expected a string or an array of strings".to_string()),
+    }
+}
+
+// title = "..." followed by one [[questions]] table per question; arrays may span lines.
+fn parse_toml_bank(contents: &str) -> ParseResult<(Option<String>, Vec<RawQuestion>)> {
+    let mut title = None;
+    let mut questions: Vec<RawQuestion> = Vec::new();
+    let mut lines = contents.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    while let Some((number, line)) = lines.next() {
+        let at = |message: String| format!("line {}: {}", number, message);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            // Tokenizing drops a trailing comment, as in "[[questions]] # first".
+            let header = toml_tokens(line).map_err(at)?;
+            let is_question = matches!(header.as_slice(),
+                [TomlToken::Open, TomlToken::Open, TomlToken::Str(name), TomlToken::Close, TomlToken::Close] if name == "questions");
+            if !is_question {
+                return Err(at(format!("unsupported table {}", line)));
+            }
+            questions.push(RawQuestion::new(format!("line {}", number)));
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| at("expected key = value".to_string()))?;
+        let key = key.trim().trim_matches('"');
+        let mut tokens = toml_tokens(value).map_err(at)?;
+        let depth = |tokens: &[TomlToken]| {
+            tokens.iter().fold(0i32, |depth, token| match token {
+                TomlToken::Open => depth + 1,
+                TomlToken::Close => depth - 1,
+                _ => depth,
+            })
+        };
+        while depth(&tokens) > 0 {
+            let (_, next) = lines.next().ok_or_else(|| at("unterminated array".to_string()))?;
+            tokens.extend(toml_tokens(next).map_err(at)?);
+        }
+        let value = toml_value(tokens).map_err(at)?;
+
+        match (questions.last_mut(), key, value) {
+            (Some(question), key, value) => question.set(key, value)?,
+            (None, "title", FieldValue::Text(text)) => title = Some(text),
+            (None, key, _) => return Err(at(format!("unexpected key \"{}\" before the first [[questions]]", key))),
+        }
+    }
+
+    Ok((title, questions))
+}
+
+// "# Title", then per question a "## text" heading, "- [ ]"/"- [x]" options (plain "- " items
+// for ordering and matching) and optional "Key: value" lines such as "Type: multi", "Id: 7" or
+// "Accept: a, b". Other lines under a heading, plain "- " items included for every other
+// type, continue the question text; ``` fenced blocks are kept verbatim.
+fn parse_markdown_bank(contents: &str) -> ParseResult<(Option<String>, Vec<RawQuestion>)> {
+    const LIST_KEYS: [&str; 4] = ["answer", "accept", "matches", "tags"];
+    const TEXT_KEYS: [&str; 6] = ["id", "type", "tolerance", "unit", "max_typos", "difficulty"];
+
+    let mut title = None;
+    let mut questions: Vec<RawQuestion> = Vec::new();
+    // Where a second option was checked, reported unless the question turns out to be multi-select.
+    let mut second_checks: Vec<Option<usize>> = Vec::new();
+    // Body lines after each heading, flagged when they are plain "- " items. "Type:" may come
+    // after the items, so whether those are options is only known once the file is read.
+    let mut bodies: Vec<Vec<(bool, String)>> = Vec::new();
+    let mut in_fence = false;
+
+    for (index, raw_line) in contents.lines().enumerate() {
+        let number = index + 1;
+        let line = raw_line.trim();
+
+        // Inside ``` fences nothing is markup: "# build it" is code, not a heading.
+        let fence = line.starts_with("```");
+        if fence || in_fence {
+            in_fence ^= fence;
+            if let Some(body) = bodies.last_mut() {
+                body.push((false, raw_line.trim_end().to_string()));
+            }
+            continue;
+        }
+
+        if let Some(heading) = line.strip_prefix("## ") {
+            let mut question = RawQuestion::new(format!("line {}", number));
+            question.text = Some(heading.trim().to_string());
+            questions.push(question);
+            second_checks.push(None);
+            bodies.push(Vec::new());
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("# ") {
+            if title.is_none() && questions.is_empty() {
+                title = Some(heading.trim().to_string());
+            }
+            continue;
+        }
+        let Some(question) = questions.last_mut() else {
+            continue;
+        };
+
+        let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+        let option = item.and_then(|item| {
+            item.strip_prefix("[ ] ").map(|text| (false, text))
+                .or_else(|| item.strip_prefix("[x] ").or_else(|| item.strip_prefix("[X] ")).map(|text| (true, text)))
+        });
+        let field = line.split_once(':').and_then(|(key, value)| {
+            let key = key.trim().to_lowercase().replace(' ', "_");
+            if LIST_KEYS.contains(&key.as_str()) {
+                let items = value.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect();
+                Some((key, FieldValue::List(items)))
+            } else if TEXT_KEYS.contains(&key.as_str()) {
+                Some((key, FieldValue::Text(value.trim().to_string())))
+            } else {
+                None
+            }
+        });
+
+        if let Some((checked, text)) = option {
+            if checked {
+                if !question.answers.is_empty() {
+                    second_checks.last_mut().unwrap().get_or_insert(number);
+                }
+                question.answers.push(Question::option_letter(question.options.len()).to_string());
+            }
+            question.options.push(text.trim().to_string());
+        } else if let Some((key, value)) = field {
+            match (key.as_str(), value) {
+                ("accept", FieldValue::List(items)) => question.accept.extend(items),
+                (key, value) => question.set(key, value)?,
+            }
+        } else if !line.is_empty() {
+            bodies.last_mut().unwrap().push((item.is_some(), line.to_string()));
+        }
+    }
+
+    for (question, body) in questions.iter_mut().zip(bodies) {
+        let items_are_options = matches!(question.kind.as_deref(), Some("ordering" | "matching"));
+        for (is_item, line) in body {
+            if is_item && items_are_options {
+                question.options.push(line[2..].trim().to_string());
+            } else if let Some(text) = question.text.as_mut() {
+                text.push('\n');
+                text.push_str(&line);
+            }
+        }
+    }
+
+    for (question, second_check) in questions.iter().zip(second_checks) {
+        if let (Some(number), false) = (second_check, question.kind.as_deref() == Some("multi")) {
+            return Err(format!("line {}: only one option may be checked", number));
+        }
+    }
+
+    Ok((title, questions))
+}
+
+#[derive(Debug)]
+struct QuizBank {
+    /// Identifies the bank in study history; the file name without its extension.
+    key: String,
+    title: String,
+    questions: Vec<QuizItem>,
+    /// Tags and difficulty by question id; questions without any use the defaults.
+    meta: HashMap<usize, QuestionMeta>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct QuestionMeta {
+    tags: Vec<String>,
+    difficulty: Difficulty,
+}
+
+impl QuizBank {
+    fn parse(contents: &str, format: BankFormat) -> ParseResult<Self> {
+        let (title, raw_questions) = match format {
+            BankFormat::Json => parse_json_bank(contents)?,
+            BankFormat::Toml => parse_toml_bank(contents)?,
+            BankFormat::Markdown => parse_markdown_bank(contents)?,
+        };
+
+        let mut questions = Vec::new();
+        let mut meta = HashMap::new();
+        for question in raw_questions {
+            let id = question.id()?;
+            if meta.insert(id, question.meta()?).is_some() {
+                return Err(format!("{}: duplicate id {} (give repeated questions distinct ids)", question.location, id));
+            }
+            questions.push(question.into_item(id)?);
+        }
+        if questions.is_empty() {
+            return Err("the bank has no questions".to_string());
+        }
+
+        Ok(Self {
+            key: String::new(),
+            title: title.unwrap_or_default(),
+            questions,
+            meta,
+        })
+    }
+
+    fn difficulty(&self, question: &QuizItem) -> Difficulty {
+        self.meta.get(&question.id()).map(|meta| meta.difficulty).unwrap_or_default()
+    }
+
+    // Keeps questions carrying any of the tags, compared case-insensitively.
+    fn retain_tagged(&mut self, tags: &[String]) {
+        let meta = &self.meta;
+        self.questions.retain(|question| {
+            meta.get(&question.id())
+                .is_some_and(|meta| meta.tags.iter().any(|tag| tags.iter().any(|wanted| wanted.eq_ignore_ascii_case(tag))))
+        });
+    }
+
+    fn shuffle(&mut self, rng: &mut SeededRng) {
+        rng.shuffle(&mut self.questions);
+        for question in &mut self.questions {
+            question.shuffle_options(rng);
+        }
+    }
+
+    // Untitled banks are named after their file.
+    fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let invalid = |message: String| QuizError::InvalidBank { source: path.display().to_string(), message };
+
+        let format = BankFormat::from_path(path)
+            .ok_or_else(|| invalid("expected a .json, .toml or .md file".to_string()))?;
+        let mut bank = Self::parse(&fs::read_to_string(path)?, format).map_err(invalid)?;
+        bank.key = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        if bank.title.is_empty() {
+            bank.title = bank.key.clone();
+        }
+        Ok(bank)
+    }
+}
+
+fn find_banks(directory: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_file() && BankFormat::from_path(&path).is_some() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+// Lists every bank in the directory, including broken ones so authors can see why they're missing.
+fn choose_bank(directory: impl AsRef<Path>) -> Result<QuizBank> {
+    let directory = directory.as_ref();
+    let paths = find_banks(directory)?;
+    if paths.is_empty() {
+        return Err(QuizError::InvalidInput(format!("No quiz banks found in {}", directory.display())));
+    }
+
+    let mut banks: Vec<Option<QuizBank>> = Vec::new();
+    println!("=== Quiz Banks ({}) ===", directory.display());
+    for (i, path) in paths.iter().enumerate() {
+        match QuizBank::load(path) {
+            Ok(bank) => {
+                println!("{}) {} ({} questions)", i + 1, bank.title, bank.questions.len());
+                banks.push(Some(bank));
+            }
+            Err(e) => {
+                println!("{}) unavailable - {}", i + 1, e);
+                banks.push(None);
+            }
+        }
+    }
+
+    loop {
+        print!("Choose a quiz (1-{}): ", banks.len());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Err(QuizError::InvalidInput("No quiz chosen".to_string()));
+        }
+
+        match input.trim().parse::<usize>() {
+            Ok(choice) if (1..=banks.len()).contains(&choice) => {
+                if let Some(bank) = banks[choice - 1].take() {
+                    println!();
+                    return Ok(bank);
+                }
+                println!("That quiz bank could not be loaded.");
+            }
+            _ => println!("Please enter a number between 1 and {}.", banks.len()),
+        }
+    }
+}
+
+const STUDY_HISTORY_FILE: &str = "data/study-history.tsv";
+const NEW_QUESTIONS_PER_DAY: usize = 10;
+// SM-2 calls a card mature once its interval reaches three weeks.
+const MATURE_INTERVAL_DAYS: u64 = 21;
+
+// Days since 1970-01-01 in UTC; std has no time zones.
+fn today() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0)
+}
+
+// Howard Hinnant's days-to-civil conversion.
+fn format_day(day: u64) -> String {
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let d = day_of_year - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = year_of_era + era * 400 + i64::from(m <= 2);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ReviewLog {
+    day: u64,
+    bank: String,
+    question_id: usize,
+    /// SM-2 grade from 0 (no answer) to 5 (quick and correct).
+    quality: u8,
+}
+
+impl ReviewLog {
+    // Skipped and wrong answers fail the review; correct ones grade by how quickly they came.
+    fn from_answer(day: u64, bank: &str, question_id: usize, record: &AnswerRecord) -> Self {
+        let quality = match (&record.input, record.correct, record.elapsed.as_secs()) {
+            (None, _, _) => 0,
+            (Some(_), false, _) => 1,
+            (Some(_), true, 0..=5) => 5,
+            (Some(_), true, 6..=15) => 4,
+            (Some(_), true, _) => 3,
+        };
+        Self { day, bank: bank.to_string(), question_id, quality }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ReviewState {
+    repetitions: u32,
+    interval_days: u64,
+    ease: f64,
+    due_day: u64,
+}
+
+impl Default for ReviewState {
+    fn default() -> Self {
+        Self {
+            repetitions: 0,
+            interval_days: 0,
+            ease: 2.5,
+            due_day: 0,
+        }
+    }
+}
+
+impl ReviewState {
+    // SM-2: intervals of 1 and 6 days, then growing by the ease factor; a failed review
+    // starts the card over. Ease moves with every grade but never drops below 1.3.
+    fn review(&mut self, day: u64, quality: u8) {
+        if quality >= 3 {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval_days as f64 * self.ease).round() as u64,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        }
+
+        let miss = f64::from(5 - quality.min(5));
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(1.3);
+        self.due_day = day + self.interval_days;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct DayProgress {
+    day: u64,
+    reviews: usize,
+    correct: usize,
+}
+
+// Append-only log of every review; schedules are rebuilt by replaying it, so the file is
+// the whole history and never needs rewriting.
+struct StudyHistory {
+    path: PathBuf,
+    reviews: Vec<ReviewLog>,
+}
+
+impl StudyHistory {
+    fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let reviews = match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, reviews })
+    }
+
+    // One review per line: day, bank, question id and quality, separated by tabs.
+    fn parse(contents: &str) -> Result<Vec<ReviewLog>> {
+        let mut reviews = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: &str| QuizError::InvalidHistory { line: index + 1, message: message.to_string() };
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [day, bank, question_id, quality] = fields.as_slice() else {
+                return Err(invalid("expected 4 tab-separated fields"));
+            };
+            reviews.push(ReviewLog {
+                day: day.parse().map_err(|_| invalid("day is not a number"))?,
+                bank: bank.to_string(),
+                question_id: question_id.parse().map_err(|_| invalid("question id is not a number"))?,
+                quality: quality.parse().ok().filter(|&q| q <= 5).ok_or_else(|| invalid("quality must be 0-5"))?,
+            });
+        }
+        Ok(reviews)
+    }
+
+    fn record(&mut self, reviews: Vec<ReviewLog>) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let is_new = !self.path.exists();
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        if is_new {
+            writeln!(file, "# day\tbank\tquestion\tquality")?;
+        }
+        for review in &reviews {
+            writeln!(file, "{}\t{}\t{}\t{}", review.day, review.bank, review.question_id, review.quality)?;
+        }
+
+        self.reviews.extend(reviews);
+        Ok(())
+    }
+
+    fn states(&self, bank: &str) -> HashMap<usize, ReviewState> {
+        let mut states: HashMap<usize, ReviewState> = HashMap::new();
+        for review in self.reviews.iter().filter(|review| review.bank == bank) {
+            states.entry(review.question_id).or_default().review(review.day, review.quality);
+        }
+        states
+    }
+
+    fn daily_progress(&self, bank: &str) -> Vec<DayProgress> {
+        let mut days: Vec<DayProgress> = Vec::new();
+        for review in self.reviews.iter().filter(|review| review.bank == bank) {
+            let index = match days.iter().position(|progress| progress.day == review.day) {
+                Some(index) => index,
+                None => {
+                    days.push(DayProgress { day: review.day, ..DayProgress::default() });
+                    days.len() - 1
+                }
+            };
+            days[index].reviews += 1;
+            days[index].correct += usize::from(review.quality >= 3);
+        }
+        days.sort_by_key(|progress| progress.day);
+        days
+    }
+}
+
+// Overdue questions first (most overdue leading), then a few never-seen ones in bank order.
+fn study_order(items: &[QuizItem], states: &HashMap<usize, ReviewState>, today: u64) -> Vec<QuizItem> {
+    let mut due: Vec<&QuizItem> = items.iter()
+        .filter(|item| states.get(&item.id()).is_some_and(|state| state.due_day <= today))
+        .collect();
+    due.sort_by_key(|item| (states[&item.id()].due_day, item.id()));
+
+    let new = items.iter()
+        .filter(|item| !states.contains_key(&item.id()))
+        .take(NEW_QUESTIONS_PER_DAY);
+
+    due.into_iter().chain(new).cloned().collect()
+}
+
+// New, learning and mature counts over the given questions only.
+fn question_counts(items: &[QuizItem], states: &HashMap<usize, ReviewState>) -> (usize, usize, usize) {
+    let (mut new, mut learning, mut mature) = (0, 0, 0);
+    for item in items {
+        match states.get(&item.id()) {
+            None => new += 1,
+            Some(state) if state.interval_days >= MATURE_INTERVAL_DAYS => mature += 1,
+            Some(_) => learning += 1,
+        }
+    }
+    (new, learning, mature)
+}
+
+fn display_progress(bank: &QuizBank, history: &StudyHistory, today: u64) {
+    // The history can hold questions a filtered or edited bank no longer has.
+    let mut states = history.states(&bank.key);
+    states.retain(|&id, _| bank.questions.iter().any(|item| item.id() == id));
+
+    println!("=== Study Progress: {} ===", bank.title);
+    println!("{:<12}{:>8}{:>9}", "Day", "Reviews", "Correct");
+    for progress in history.daily_progress(&bank.key) {
+        println!("{:<12}{:>8}{:>8.0}%", format_day(progress.day), progress.reviews,
+                 progress.correct as f64 / progress.reviews as f64 * 100.0);
+    }
+
+    let (new, learning, mature) = question_counts(&bank.questions, &states);
+    println!("Questions: {} new | {} learning | {} mature", new, learning, mature);
+
+    let due_by = |day: u64| states.values().filter(|state| state.due_day <= day).count();
+    print!("Due now: {} | Due tomorrow: {}", due_by(today), due_by(today + 1) - due_by(today));
+    match states.values().map(|state| state.due_day).filter(|&day| day > today).min() {
+        Some(day) => println!(" | Next review: {}", format_day(day)),
+        None => println!(),
+    }
+}
+
+fn run_study(bank: QuizBank, limits: TimeLimits, history_file: &str, today: u64) -> Result<()> {
+    let mut history = StudyHistory::load(history_file)?;
+    let questions = study_order(&bank.questions, &history.states(&bank.key), today);
+
+    if questions.is_empty() {
+        println!("Nothing to review today. 🎉\n");
+    } else {
+        let mut session = QuizSession::new(questions).with_time_limits(limits);
+        session.execute()?;
+
+        let reviews = session.questions.iter()
+            .zip(&session.answers)
+            .map(|(item, record)| ReviewLog::from_answer(today, &bank.key, item.id(), record))
+            .collect();
+        history.record(reviews)?;
+        println!();
+    }
+
+    display_progress(&bank, &history, today);
+    Ok(())
+}
+
+fn sample_bank() -> QuizBank {
+    let quiz = QuizBuilder::new()
+        .add_question(
+            "What is the capital of France?",
+            ["London", "Paris", "Berlin"],
+            ["Paris"],
+            'b',
+        )
+        .add_question(
+            "What is 5 + 3?",
+            ["7", "8", "9"],
+            ["8"],
+            'b',
+        )
+        .add_question(
+            "Which planet is closest to the Sun?",
+            ["Venus", "Mercury", "Earth"],
+            ["Mercury"],
+            'b',
+        )
+        .add_true_false("Light travels faster than sound.", true)
+        .add_numeric("How many days are in a year, to the nearest day?", 365.25, 1.0)
+        .build();
+
+    QuizBank {
+        key: "sample".to_string(),
+        title: "Sample Quiz".to_string(),
+        questions: quiz.questions,
+        meta: HashMap::new(),
+    }
+}
+
+fn parse_seconds(flag: &str, value: Option<String>) -> Result<Duration> {
+    value.as_deref()
+        .and_then(|value| value.parse::<f64>().ok())
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| QuizError::InvalidInput(format!("{} needs a positive number of seconds", flag)))
+}
+
+const RESULTS_FILE: &str = "data/quiz-results.jsonl";
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn format_timestamp(seconds: u64) -> String {
+    format!("{} {:02}:{:02} UTC", format_day(seconds / 86_400), seconds % 86_400 / 3600, seconds % 3600 / 60)
+}
+
+// Question text and answers are stored as shown, so reviews stay right after banks change
+// or options are shuffled.
+#[derive(Debug, Clone, PartialEq)]
+struct AttemptAnswer {
+    question_id: usize,
+    question: String,
+    difficulty: Difficulty,
+    given: Option<String>,
+    correct_answer: String,
+    correct: bool,
+    seconds: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Attempt {
+    user: String,
+    /// Counts up per user, across all banks.
+    attempt: usize,
+    timestamp: u64,
+    bank: String,
+    title: String,
+    score: usize,
+    grade: f64,
+    answers: Vec<AttemptAnswer>,
+}
+
+impl Attempt {
+    fn from_session(user: &str, attempt: usize, timestamp: u64, bank: &QuizBank, session: &QuizSession<QuizItem>) -> Self {
+        let answers = session.questions.iter()
+            .zip(&session.answers)
+            .map(|(item, record)| AttemptAnswer {
+                question_id: item.id(),
+                question: item.text().to_string(),
+                difficulty: bank.difficulty(item),
+                given: record.input.clone(),
+                correct_answer: item.correct_answer(),
+                correct: record.correct,
+                seconds: record.elapsed.as_secs_f64(),
+            })
+            .collect();
+
+        Self {
+            user: user.to_string(),
+            attempt,
+            timestamp,
+            bank: bank.key.clone(),
+            title: bank.title.clone(),
+            score: session.score,
+            grade: session.grade(),
+            answers,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let answers: Vec<String> = self.answers.iter()
+            .map(|answer| format!(
+                "{{\"id\":{},\"question\":{},\"difficulty\":{},\"given\":{},\"correct_answer\":{},\"correct\":{},\"seconds\":{:.3}}}",
+                answer.question_id,
+                json_string(&answer.question),
+                json_string(answer.difficulty.name()),
+                answer.given.as_deref().map_or("null".to_string(), json_string),
+                json_string(&answer.correct_answer),
+                answer.correct,
+                answer.seconds
+            ))
+            .collect();
+
+        format!(
+            "{{\"user\":{},\"attempt\":{},\"timestamp\":{},\"bank\":{},\"title\":{},\"score\":{},\"grade\":{:.1},\"answers\":[{}]}}",
+            json_string(&self.user),
+            self.attempt,
+            self.timestamp,
+            json_string(&self.bank),
+            json_string(&self.title),
+            self.score,
+            self.grade,
+            answers.join(",")
+        )
+    }
+
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        let answers = match value.get("answers")? {
+            JsonValue::Array(answers) => answers.iter()
+                .map(|answer| Some(AttemptAnswer {
+                    question_id: answer.get("id")?.as_f64()? as usize,
+                    question: answer.get("question")?.as_str()?.to_string(),
+                    difficulty: Difficulty::parse(answer.get("difficulty")?.as_str()?)?,
+                    given: match answer.get("given")? {
+                        JsonValue::Null => None,
+                        given => Some(given.as_str()?.to_string()),
+                    },
+                    correct_answer: answer.get("correct_answer")?.as_str()?.to_string(),
+                    correct: answer.get("correct")?.as_bool()?,
+                    seconds: answer.get("seconds")?.as_f64()?,
+                }))
+                .collect::<Option<Vec<_>>>()?,
+            _ => return None,
+        };
+
+        Some(Self {
+            user: value.get("user")?.as_str()?.to_string(),
+            attempt: value.get("attempt")?.as_f64()? as usize,
+            timestamp: value.get("timestamp")?.as_f64()? as u64,
+            bank: value.get("bank")?.as_str()?.to_string(),
+            title: value.get("title")?.as_str()?.to_string(),
+            score: value.get("score")?.as_f64()? as usize,
+            grade: value.get("grade")?.as_f64()?,
+            answers,
+        })
+    }
+
+    fn review(&self) -> String {
+        let mut output = format!(
+            "=== Review: {} — attempt {} by {} ({}) ===\n",
+            self.title, self.attempt, self.user, format_timestamp(self.timestamp)
+        );
+        for (i, answer) in self.answers.iter().enumerate() {
+            let mark = if answer.correct { "✓" } else { "✗" };
+            output.push_str(&format!("{}. {}\n", i + 1, answer.question));
+            output.push_str(&format!("   Your answer: {} {}\n", answer.given.as_deref().unwrap_or("(no answer)"), mark));
+            if !answer.correct {
+                output.push_str(&format!("   Correct answer: {}\n", answer.correct_answer));
+            }
+        }
+        output.push_str(&format!("Score: {}/{} ({:.1}%)\n", self.score, self.answers.len(), self.grade));
+        output
+    }
+}
+
+// One attempt per line as JSON, appended after each quiz.
+struct ResultStore {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl ResultStore {
+    fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, attempts: Self::parse(&contents)? })
+    }
+
+    fn parse(contents: &str) -> Result<Vec<Attempt>> {
+        contents.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let invalid = |message: String| QuizError::InvalidResults { line: index + 1, message };
+                let value = JsonParser::parse(line).map_err(invalid)?;
+                Attempt::from_json(&value).ok_or_else(|| invalid("not a quiz attempt".to_string()))
+            })
+            .collect()
+    }
+
+    fn next_attempt(&self, user: &str) -> usize {
+        self.attempts.iter().filter(|attempt| attempt.user == user).map(|attempt| attempt.attempt).max().unwrap_or(0) + 1
+    }
+
+    // The given attempt number, or the user's latest one.
+    fn find(&self, user: &str, attempt: Option<usize>) -> Option<&Attempt> {
+        self.attempts.iter()
+            .filter(|candidate| candidate.user == user)
+            .filter(|candidate| attempt.is_none_or(|attempt| candidate.attempt == attempt))
+            .max_by_key(|candidate| candidate.attempt)
+    }
+
+    fn save(&mut self, attempt: Attempt) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", attempt.to_json())?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct QuestionStats {
+    bank: String,
+    question_id: usize,
+    question: String,
+    authored_difficulty: Difficulty,
+    attempts: usize,
+    correct: usize,
+    skipped: usize,
+    total_seconds: f64,
+}
+
+impl QuestionStats {
+    /// Share of attempts answered correctly, the classic item difficulty index.
+    fn p_value(&self) -> f64 {
+        self.correct as f64 / self.attempts as f64
+    }
+
+    // How hard the question turned out to be, whatever its author thought.
+    fn observed_difficulty(&self) -> Difficulty {
+        match self.p_value() {
+            p if p >= 0.8 => Difficulty::Easy,
+            p if p >= 0.4 => Difficulty::Medium,
+            _ => Difficulty::Hard,
+        }
+    }
+
+    fn average_seconds(&self) -> f64 {
+        self.total_seconds / self.attempts as f64
+    }
+}
+
+// Hardest questions first. Answers are matched by the question's stable id, so a question keeps
+// its stats when others are added or moved, and the latest wording of each question wins.
+fn question_stats(attempts: &[Attempt]) -> Vec<QuestionStats> {
+    let mut stats: Vec<QuestionStats> = Vec::new();
+    for attempt in attempts {
+        for answer in &attempt.answers {
+            let index = match stats.iter().position(|s| s.bank == attempt.bank && s.question_id == answer.question_id) {
+                Some(index) => index,
+                None => {
+                    stats.push(QuestionStats {
+                        bank: attempt.bank.clone(),
+                        question_id: answer.question_id,
+                        question: String::new(),
+                        authored_difficulty: answer.difficulty,
+                        attempts: 0,
+                        correct: 0,
+                        skipped: 0,
+                        total_seconds: 0.0,
+                    });
+                    stats.len() - 1
+                }
+            };
+
+            let entry = &mut stats[index];
+            entry.question = answer.question.clone();
+            entry.authored_difficulty = answer.difficulty;
+            entry.attempts += 1;
+            entry.correct += usize::from(answer.correct);
+            entry.skipped += usize::from(answer.given.is_none());
+            entry.total_seconds += answer.seconds;
+        }
+    }
+
+    stats.sort_by(|a, b| {
+        a.p_value().total_cmp(&b.p_value())
+            .then_with(|| a.bank.cmp(&b.bank))
+            .then(a.question_id.cmp(&b.question_id))
+    });
+    stats
+}
+
+fn export_csv(stats: &[QuestionStats], out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "bank,question_id,question,authored_difficulty,observed_difficulty,attempts,correct,skipped,p_value,average_seconds")?;
+    for s in stats {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{:.3},{:.1}",
+            csv_field(&s.bank), s.question_id, csv_field(&s.question), s.authored_difficulty.name(), s.observed_difficulty().name(),
+            s.attempts, s.correct, s.skipped, s.p_value(), s.average_seconds()
+        )?;
+    }
+    Ok(())
+}
+
+fn export_json(attempts: &[Attempt], stats: &[QuestionStats], out: &mut dyn Write) -> io::Result<()> {
+    let attempts: Vec<String> = attempts.iter()
+        .map(|a| format!(
+            "{{\"user\":{},\"attempt\":{},\"timestamp\":{},\"bank\":{},\"score\":{},\"questions\":{},\"grade\":{:.1}}}",
+            json_string(&a.user), a.attempt, a.timestamp, json_string(&a.bank), a.score, a.answers.len(), a.grade
+        ))
+        .collect();
+    let questions: Vec<String> = stats.iter()
+        .map(|s| format!(
+            "{{\"bank\":{},\"question_id\":{},\"question\":{},\"authored_difficulty\":\"{}\",\"observed_difficulty\":\"{}\",\
+             \"attempts\":{},\"correct\":{},\"skipped\":{},\"p_value\":{:.3},\"average_seconds\":{:.1}}}",
+            json_string(&s.bank), s.question_id, json_string(&s.question), s.authored_difficulty.name(), s.observed_difficulty().name(),
+            s.attempts, s.correct, s.skipped, s.p_value(), s.average_seconds()
+        ))
+        .collect();
+
+    writeln!(out, "{{\"attempts\":[{}],\"questions\":[{}]}}", attempts.join(","), questions.join(","))
+}
+
+fn run_export(format: &str, output: Option<&str>) -> Result<()> {
+    if !matches!(format, "csv" | "json") {
+        return Err(QuizError::InvalidInput(format!("Unknown export format {} (use csv or json)", format)));
+    }
+    let store = ResultStore::load(RESULTS_FILE)?;
+    let stats = question_stats(&store.attempts);
+
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(io::BufWriter::new(fs::File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    if format == "csv" {
+        export_csv(&stats, &mut out)?;
+    } else {
+        export_json(&store.attempts, &stats, &mut out)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn run_review(user: &str, attempt: Option<usize>) -> Result<()> {
+    let store = ResultStore::load(RESULTS_FILE)?;
+    match store.find(user, attempt) {
+        Some(attempt) => {
+            print!("{}", attempt.review());
+            Ok(())
+        }
+        None => Err(QuizError::InvalidInput(match attempt {
+            Some(number) => format!("{} has no attempt {}", user, number),
+            None => format!("{} has no saved attempts", user),
+        })),
+    }
+}
+
+fn parse_number_flag<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T> {
+    value.and_then(|value| value.parse().ok())
+        .ok_or_else(|| QuizError::InvalidInput(format!("{} needs a number", flag)))
+}
+
+fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0);
+    nanos ^ u64::from(std::process::id()).rotate_left(32)
+}
+
+// Usage: QuizApp [BANK_FILE | BANK_DIRECTORY] [--question-time SECS] [--quiz-time SECS] [--study]
+//                [--tags A,B] [--count N] [--shuffle] [--seed N] [--adaptive] [--user NAME]
+//        QuizApp --review [ATTEMPT] [--user NAME]
+//        QuizApp --export csv|json [--output FILE]
+// With no bank, banks come from data/quizzes (falling back to the built-in sample quiz).
+// Drawing a count of questions shuffles the pool; a seed makes the shuffle repeatable.
+// Finished quizzes are saved per user to data/quiz-results.jsonl.
+fn run_quiz() -> Result<()> {
+    let mut source: Option<String> = None;
+    let mut limits = TimeLimits::default();
+    let mut study = false;
+    let mut tags: Vec<String> = Vec::new();
+    let mut count: Option<usize> = None;
+    let mut shuffle = false;
+    let mut seed: Option<u64> = None;
+    let mut adaptive = false;
+    let mut user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_else(|_| "guest".to_string());
+    let mut review: Option<Option<usize>> = None;
+    let mut export: Option<String> = None;
+    let mut output: Option<String> = None;
+
+    let mut args = std::env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--question-time" => limits.per_question = Some(parse_seconds(&arg, args.next())?),
+            "--quiz-time" => limits.whole_quiz = Some(parse_seconds(&arg, args.next())?),
+            "--study" => study = true,
+            "--tags" => {
+                let value = args.next().unwrap_or_default();
+                tags = value.split(',').map(str::trim).filter(|tag| !tag.is_empty()).map(String::from).collect();
+            }
+            "--count" => count = Some(parse_number_flag::<usize>(&arg, args.next())?).filter(|&n| n > 0),
+            "--shuffle" => shuffle = true,
+            "--seed" => seed = Some(parse_number_flag(&arg, args.next())?),
+            "--adaptive" => adaptive = true,
+            "--user" => user = args.next().filter(|name| !name.trim().is_empty())
+                .ok_or_else(|| QuizError::InvalidInput("--user needs a name".to_string()))?,
+            "--review" => review = Some(args.next_if(|value| value.parse::<usize>().is_ok()).and_then(|value| value.parse().ok())),
+            "--export" => export = Some(args.next().unwrap_or_default()),
+            "--output" => output = args.next(),
+            flag if flag.starts_with("--") => {
+                return Err(QuizError::InvalidInput(format!("Unknown option {}", flag)));
+            }
+            _ => source = Some(arg),
+        }
+    }
+
+    if let Some(format) = export {
+        return run_export(&format, output.as_deref());
+    }
+    if let Some(attempt) = review {
+        return run_review(&user, attempt);
+    }
+
+    let bank = match source {
+        Some(path) if Path::new(&path).is_dir() => Some(choose_bank(&path)?),
+        Some(path) => Some(QuizBank::load(&path)?),
+        None if find_banks(QUIZ_DIRECTORY).is_ok_and(|paths| !paths.is_empty()) => Some(choose_bank(QUIZ_DIRECTORY)?),
+        None => None,
+    };
+
+    let mut bank = bank.unwrap_or_else(sample_bank);
+    if !tags.is_empty() {
+        bank.retain_tagged(&tags);
+        if bank.questions.is_empty() {
+            return Err(QuizError::InvalidInput(format!("No questions tagged {}", tags.join(", "))));
+        }
+    }
+    if shuffle || seed.is_some() || count.is_some() {
+        let seed = seed.unwrap_or_else(random_seed);
+        println!("Seed: {} (pass --seed {} to repeat this quiz)", seed, seed);
+        bank.shuffle(&mut SeededRng::new(seed));
+    }
+
+    if study {
+        if count.is_some() || adaptive {
+            return Err(QuizError::InvalidInput("--count and --adaptive don't apply to --study".to_string()));
+        }
+        return run_study(bank, limits, STUDY_HISTORY_FILE, today());
+    }
+
+    println!("{}\n", bank.title);
+    let mut store = ResultStore::load(RESULTS_FILE)?;
+    let difficulties: Vec<Difficulty> = bank.questions.iter().map(|question| bank.difficulty(question)).collect();
+    let mut quiz = QuizSession::new(bank.questions.clone()).with_time_limits(limits);
+    if let Some(count) = count {
+        quiz = quiz.with_question_limit(count);
+    }
+    if adaptive {
+        quiz = quiz.with_adaptive_difficulty(difficulties);
+    }
+    quiz.execute()?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let attempt = Attempt::from_session(&user, store.next_attempt(&user), timestamp, &bank, &quiz);
+    println!("\n{}", attempt.review());
+    println!("Saved as attempt {} for {}.", attempt.attempt, attempt.user);
+    store.save(attempt)
+}
+
+fn main() {
+    if let Err(e) = run_quiz() {
+        eprintln!("Quiz failed: {}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn choice(item: &QuizItem) -> &Question {
+        match item {
+            QuizItem::Choice(question) => question,
+            other => panic!("expected a choice question, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sample_banks_load() {
+        let banks = [
+            (include_str!("data/quizzes/general-knowledge.json"), BankFormat::Json, 10),
+            (include_str!("data/quizzes/science.toml"), BankFormat::Toml, 3),
+            (include_str!("data/quizzes/rust-basics.md"), BankFormat::Markdown, 3),
+        ];
+        for (contents, format, count) in banks {
+            let bank = QuizBank::parse(contents, format).unwrap();
+            assert!(!bank.title.is_empty());
+            assert_eq!(bank.questions.len(), count);
+        }
+    }
+
+    #[test]
+    fn test_answer_by_letter_or_text() {
+        let json = r#"{"questions": [
+            {"text": "Capital of France?", "options": ["London", "Paris"], "answer": "b"},
+            {"id": 2, "text": "5 + 3?", "options": ["7", "8"], "answer": "8", "accept": ["eight"]}
+        ]}"#;
+        let bank = QuizBank::parse(json, BankFormat::Json).unwrap();
+
+        assert_eq!(choice(&bank.questions[0]).correct_option, 'b');
+        assert_eq!(bank.questions[0].correct_answer(), "b) Paris");
+        assert!(bank.questions[0].check_answer("paris"));
+        assert_eq!(choice(&bank.questions[1]).correct_option, 'b');
+        assert!(bank.questions[1].check_answer("Eight"));
+        assert!(!bank.questions[1].check_answer("a"));
+        assert_eq!(choice(&bank.questions[1]).id, 2);
+    }
+
+    #[test]
+    fn test_markdown_bank() {
+        let markdown = "# Rust\n\n## Which macro prints a line?\n```\nfn main() {}\n```\n- [ ] print\n- [x] println\nAccept: println!\n";
+        let bank = QuizBank::parse(markdown, BankFormat::Markdown).unwrap();
+        let question = choice(&bank.questions[0]);
+
+        assert_eq!(bank.title, "Rust");
+        assert_eq!(question.text, "Which macro prints a line?\n```\nfn main() {}\n```");
+        assert_eq!(question.options, ["print", "println"]);
+        assert!(question.check_answer("println!"));
+
+        let markdown = "## Which of these compile?\n- `let x = 5;`\n- `let y: u8 = 256;`\n- [x] Only the first\n- [ ] Both\n";
+        let question = QuizBank::parse(markdown, BankFormat::Markdown).unwrap().questions.remove(0);
+        assert_eq!(question.text(), "Which of these compile?\n- `let x = 5;`\n- `let y: u8 = 256;`");
+        assert_eq!(choice(&question).options, ["Only the first", "Both"]);
+
+        let markdown = "## Run this:\n```\n# build it\n## not a question\n- [x] not an option\nType: multi\n    cargo build\n```\n- [ ] yes\n- [x] no\n";
+        let bank = QuizBank::parse(markdown, BankFormat::Markdown).unwrap();
+        assert_eq!(bank.questions.len(), 1);
+        assert_eq!(
+            choice(&bank.questions[0]).text,
+            "Run this:\n```\n# build it\n## not a question\n- [x] not an option\nType: multi\n    cargo build\n```"
+        );
+        assert_eq!(choice(&bank.questions[0]).options, ["yes", "no"]);
+    }
+
+    #[test]
+    fn test_toml_multiline_arrays_and_comments() {
+        let toml = "title = 'Maths' # a comment\n[[questions]] # first\ntext = \"Pick \\\"two\\\"\"\noptions = [\n  \"1\", # one\n  \"2\",\n]\nanswer = \"2\"\n";
+        let bank = QuizBank::parse(toml, BankFormat::Toml).unwrap();
+
+        assert_eq!(bank.title, "Maths");
+        assert_eq!(choice(&bank.questions[0]).text, "Pick \"two\"");
+        assert_eq!(choice(&bank.questions[0]).correct_option, 'b');
+    }
+
+    #[test]
+    fn test_invalid_banks_are_rejected() {
+        let cases = [
+            (r#"{"questions": [{"text": "Q", "options": ["only"], "answer": "a"}]}"#, BankFormat::Json, "between 2 and 26"),
+            (r#"{"questions": [{"text": "Q", "options": ["x", "y"], "answer": "d"}]}"#, BankFormat::Json, "not between 'a' and 'b'"),
+            (r#"{"questions": [{"text": "Q", "options": ["x", "y"], "answer": "z!"}]}"#, BankFormat::Json, "is not one of the options"),
+            (r#"{"questions": [{"text": "Q", "options": ["x", "X"], "answer": "a"}]}"#, BankFormat::Json, "appears twice"),
+            (r#"{"questions": [{"text": "Q", "options": ["x", "y"]}]}"#, BankFormat::Json, "missing answer"),
+            (r#"{"questions": [{"text": "Q", "options": [1, 2], "answer": "a"}]}"#, BankFormat::Json, "cannot be an array"),
+            (r#"{"questions": []}"#, BankFormat::Json, "no questions"),
+            ("{\"questions\": [\n  {\"text\": \"Q\",}\n]}", BankFormat::Json, "line 2"),
+            ("[[questions]]\ntext = \"Q\"\noptions = [\"x\", \"y\"\n", BankFormat::Toml, "unterminated array"),
+            ("[[questions]]\ncolour = \"red\"\n", BankFormat::Toml, "unknown field"),
+            ("## Q\n- [x] a\n- [x] b\n", BankFormat::Markdown, "only one option"),
+            ("## Q\n- [ ] a\n- [ ] b\n", BankFormat::Markdown, "missing answer"),
+            ("[[questions]]\nid = 3\ntext = \"A\"\ntype = \"true_false\"\nanswer = \"true\"\n[[questions]]\nid = 3\ntext = \"B\"\ntype = \"true_false\"\nanswer = \"true\"\n", BankFormat::Toml, "duplicate id 3"),
+            ("## Q\nType: true_false\nAnswer: true\n## Q\nType: true_false\nAnswer: false\n", BankFormat::Markdown, "duplicate id"),
+            ("## Q\nId: first\n- [x] a\n- [ ] b\n", BankFormat::Markdown, "positive whole number"),
+        ];
+
+        for (contents, format, expected) in cases {
+            let error = QuizBank::parse(contents, format).unwrap_err();
+            assert!(error.contains(expected), "{:?} gave {:?}", contents, error);
+        }
+    }
+
+    #[test]
+    fn test_json_string_escapes() {
+        let value = JsonParser::parse(r#"["tab\there", "\u00e9\ud83e\udd80", "quote\""]"#).unwrap();
+        assert_eq!(value, JsonValue::Array(vec![
+            JsonValue::String("tab\there".to_string()),
+            JsonValue::String("é🦀".to_string()),
+            JsonValue::String("quote\"".to_string()),
+        ]));
+    }
+
+    #[test]
+    fn test_multi_select_and_true_false() {
+        let multi = MultiSelectQuestion::new(1, "Primes?", ["2", "4", "7"], [2, 0]);
+        assert!(multi.check_answer("a, c"));
+        assert!(multi.check_answer("CA"));
+        assert!(multi.check_answer("7, 2"));
+        assert!(!multi.check_answer("a"));
+        assert!(!multi.check_answer("a, b, c"));
+        assert!(!multi.check_answer("a, z"));
+        assert_eq!(multi.correct_answer(), "a) 2, c) 7");
+
+        let true_false = TrueFalseQuestion::new(2, "Rust has a garbage collector.", false);
+        assert!(true_false.check_answer("False"));
+        assert!(true_false.check_answer("n"));
+        assert!(!true_false.check_answer("true"));
+        assert!(!true_false.check_answer("maybe"));
+    }
+
+    #[test]
+    fn test_numeric_tolerance_and_units() {
+        let question = NumericQuestion::new(1, "g?", 9.81, 0.05).with_unit("m/s²");
+        assert!(question.check_answer("9.81"));
+        assert!(question.check_answer("9.8 m/s²"));
+        assert!(question.check_answer("9.86"));
+        assert!(!question.check_answer("9.9"));
+        assert!(!question.check_answer("nine"));
+        assert_eq!(question.correct_answer(), "9.81 (±0.05) m/s²");
+
+        let exact = NumericQuestion::new(2, "5 + 3?", 8.0, 0.0);
+        assert!(exact.check_answer("8"));
+        assert!(!exact.check_answer("8.1"));
+    }
+
+    #[test]
+    fn test_free_text_fuzzy_matching() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("crab", "crab"), 0);
+
+        let question = FreeTextQuestion::new(1, "Mascot?", ["Ferris", "Ferris the crab"]);
+        assert!(question.check_answer("ferris"));
+        assert!(question.check_answer("Feris"));
+        assert!(question.check_answer("  FERRIS THE  CRAB! "));
+        assert!(!question.check_answer("Fred"));
+
+        let short = FreeTextQuestion::new(2, "Symbol for gold?", ["Au"]);
+        assert!(!short.check_answer("Ag"));
+        assert!(short.with_max_typos(1).check_answer("Ag"));
+
+        let accented = FreeTextQuestion::new(3, "Irish name for Ireland?", ["Éire"]).with_max_typos(0);
+        assert!(accented.check_answer("éire"));
+        assert!(accented.check_answer("ÉIRE"));
+    }
+
+    #[test]
+    fn test_ordering_and_matching() {
+        let ordering = OrderingQuestion::new(1, "Order", ["Mars", "Mercury", "Earth"], [1, 2, 0]);
+        assert!(ordering.check_answer("b, c, a"));
+        assert!(ordering.check_answer("bca"));
+        assert!(ordering.check_answer("Mercury, Earth, Mars"));
+        assert!(!ordering.check_answer("a, b, c"));
+        assert!(!ordering.check_answer("b, c"));
+
+        let matching = MatchingQuestion::new(2, "Match", ["Python", "Rust"], ["Hoare", "van Rossum"], [1, 0]);
+        assert!(matching.check_answer("b a"));
+        assert!(matching.check_answer("2a, 1b"));
+        assert!(matching.check_answer("1-b 2-a"));
+        assert!(!matching.check_answer("a b"));
+        assert!(!matching.check_answer("1b"));
+        assert!(!matching.check_answer("1b 1a"));
+        assert!(!matching.check_answer("1b 3a"));
+    }
+
+    #[test]
+    fn test_mixed_bank() {
+        let bank = QuizBank::parse(include_str!("data/quizzes/mixed-bag.toml"), BankFormat::Toml).unwrap();
+        let kinds: Vec<&str> = bank.questions.iter()
+            .map(|item| match item {
+                QuizItem::Choice(_) => "choice",
+                QuizItem::MultiSelect(_) => "multi",
+                QuizItem::TrueFalse(_) => "true_false",
+                QuizItem::Numeric(_) => "numeric",
+                QuizItem::FreeText(_) => "text",
+                QuizItem::Ordering(_) => "ordering",
+                QuizItem::Matching(_) => "matching",
+            })
+            .collect();
+        assert_eq!(kinds, ["multi", "true_false", "numeric", "text", "ordering", "matching", "choice"]);
+
+        let answers = ["a, c", "false", "9.8", "Ferris", "b, d, a, c", "1c 2a 3b", "c"];
+        for (item, answer) in bank.questions.iter().zip(answers) {
+            assert!(item.check_answer(answer), "{} rejected {:?}", item.display(), answer);
+        }
+
+        let markdown = "## Pick the even numbers\nType: multi\n- [x] 2\n- [ ] 3\n- [x] 4\n\n## Sort\nType: ordering\n- b\n- a\nAnswer: b, a\n## Sort later\n- y\n- x\nType: ordering\nAnswer: b, a\n";
+        let bank = QuizBank::parse(markdown, BankFormat::Markdown).unwrap();
+        assert!(bank.questions[0].check_answer("a, c"));
+        assert!(bank.questions[1].check_answer("b a"));
+        assert!(bank.questions[2].check_answer("x, y"));
+    }
+
+    #[test]
+    fn test_invalid_question_types_are_rejected() {
+        let cases = [
+            ("[[questions]]\ntype = \"essay\"\ntext = \"Q\"\nanswer = \"x\"", "unknown question type"),
+            ("[[questions]]\ntype = \"true_false\"\ntext = \"Q\"\nanswer = \"perhaps\"", "true or false"),
+            ("[[questions]]\ntype = \"true_false\"\ntext = \"Q\"\noptions = [\"x\", \"y\"]\nanswer = true", "not used by true_false"),
+            ("[[questions]]\ntype = \"numeric\"\ntext = \"Q\"\nanswer = \"ten\"", "not a valid number"),
+            ("[[questions]]\ntype = \"numeric\"\ntext = \"Q\"\nanswer = 1\ntolerance = -1", "non-negative"),
+            ("[[questions]]\ntype = \"multi\"\ntext = \"Q\"\noptions = [\"x\", \"y\"]\nanswer = [\"a\", \"q\"]", "is not one of the options"),
+            ("[[questions]]\ntype = \"ordering\"\ntext = \"Q\"\noptions = [\"x\", \"y\"]\nanswer = \"a, a\"", "every item exactly once"),
+            ("[[questions]]\ntype = \"matching\"\ntext = \"Q\"\noptions = [\"x\", \"y\"]\nmatches = [\"1\", \"2\"]\nanswer = \"a\"", "one option for each"),
+            ("[[questions]]\ntext = \"Q\"\noptions = [\"x\", \"y\"]\nanswer = [\"a\", \"b\"]", "single answer"),
+        ];
+
+        for (contents, expected) in cases {
+            let error = QuizBank::parse(contents, BankFormat::Toml).unwrap_err();
+            assert!(error.contains(expected), "{:?} gave {:?}", contents, error);
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeClock {
+        now: std::cell::Cell<Duration>,
+    }
+
+    impl TimeProvider for FakeClock {
+        fn now(&self) -> Duration {
+            self.now.get()
+        }
+    }
+
+    // Each answer arrives after its delay, unless the timeout comes first.
+    struct ScriptedAnswers {
+        clock: Rc<FakeClock>,
+        answers: std::collections::VecDeque<(u64, &'static str)>,
+    }
+
+    impl AnswerSource for ScriptedAnswers {
+        fn next_answer(&mut self, timeout: Option<Duration>) -> Result<Option<String>> {
+            let (seconds, answer) = self.answers.pop_front().unwrap_or((0, ""));
+            let delay = Duration::from_secs(seconds);
+            if let Some(timeout) = timeout.filter(|&timeout| delay > timeout) {
+                self.clock.now.set(self.clock.now() + timeout);
+                return Ok(None);
+            }
+            self.clock.now.set(self.clock.now() + delay);
+            Ok(Some(answer.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_late_answers_are_discarded_after_timeout() {
+        let (sender, receiver) = mpsc::channel();
+        let mut input = StdinAnswers { receiver: Some(receiver), discard_pending: false };
+        let short = Some(Duration::from_millis(10));
+
+        sender.send(Ok("early".to_string())).unwrap();
+        assert_eq!(input.next_answer(short).unwrap().as_deref(), Some("early"));
+        assert_eq!(input.next_answer(short).unwrap(), None);
+
+        // Typed just after the first question expired, before the second one was read.
+        sender.send(Ok("late".to_string())).unwrap();
+        assert_eq!(input.next_answer(short).unwrap(), None);
+
+        // Only what arrives while the next question is waiting counts as its answer.
+        sender.send(Ok("stale".to_string())).unwrap();
+        let typist = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            sender.send(Ok("second".to_string())).unwrap();
+            sender.send(Ok("third".to_string())).unwrap();
+        });
+        assert_eq!(input.next_answer(None).unwrap().as_deref(), Some("second"));
+        assert_eq!(input.next_answer(short).unwrap().as_deref(), Some("third"));
+
+        typist.join().unwrap();
+        assert_eq!(input.next_answer(None).unwrap().as_deref(), Some(""));
+    }
+
+    fn timed_session(limits: TimeLimits, answers: &[(u64, &'static str)]) -> QuizSession<QuizItem> {
+        let clock = Rc::new(FakeClock::default());
+        let input = ScriptedAnswers { clock: clock.clone(), answers: answers.iter().copied().collect() };
+        let questions = (1..=4).map(|id| TrueFalseQuestion::new(id, "True?", true).into()).collect();
+        QuizSession::with_io(questions, clock, Box::new(input)).with_time_limits(limits)
+    }
+
+    #[test]
+    fn test_untimed_grade_ignores_speed() {
+        let mut quiz = timed_session(TimeLimits::default(), &[(1, "t"), (100, "t"), (5, "f"), (1000, "t")]);
+        quiz.execute().unwrap();
+
+        assert_eq!(quiz.score, 3);
+        assert_eq!(quiz.grade(), 75.0);
+        assert_eq!(quiz.answers[3].elapsed, Duration::from_secs(1000));
+    }
+
+    #[test]
+    fn test_question_timeout_skips_question() {
+        let limits = TimeLimits { per_question: Some(Duration::from_secs(10)), whole_quiz: None };
+        let mut quiz = timed_session(limits, &[(0, "t"), (11, "t"), (5, "t"), (10, "f")]);
+        quiz.execute().unwrap();
+
+        assert_eq!(quiz.score, 2);
+        assert_eq!(quiz.answers[1], AnswerRecord { input: None, correct: false, elapsed: Duration::from_secs(10) });
+        assert_eq!(quiz.answers[3].input.as_deref(), Some("f"));
+        // Instant answer scores 1, half the budget scores 0.75.
+        assert_eq!(quiz.grade(), (1.0 + 0.75) / 4.0 * 100.0);
+    }
+
+    #[test]
+    fn test_quiz_timeout_skips_remaining_questions() {
+        let limits = TimeLimits { per_question: None, whole_quiz: Some(Duration::from_secs(20)) };
+        let mut quiz = timed_session(limits, &[(5, "t"), (10, "t"), (30, "t"), (0, "t")]);
+        quiz.execute().unwrap();
+
+        // The third question only gets the 5 seconds left on the quiz clock.
+        assert_eq!(quiz.score, 2);
+        assert_eq!(quiz.answers.len(), 4);
+        assert_eq!(quiz.answers[2].elapsed, Duration::from_secs(5));
+        assert!(quiz.answers[2..].iter().all(|record| record.input.is_none()));
+        // Each question gets a quarter of the quiz (5s); both answers used it all, scoring 0.5.
+        assert_eq!(quiz.grade(), 25.0);
+    }
+
+    #[test]
+    fn test_faster_answers_score_higher() {
+        let limits = TimeLimits { per_question: Some(Duration::from_secs(20)), whole_quiz: None };
+        let mut fast = timed_session(limits, &[(2, "t"), (2, "t"), (2, "t"), (2, "t")]);
+        let mut slow = timed_session(limits, &[(18, "t"), (18, "t"), (18, "t"), (18, "t")]);
+        fast.execute().unwrap();
+        slow.execute().unwrap();
+
+        assert_eq!(fast.score, slow.score);
+        assert!(fast.grade() > slow.grade());
+        assert!(slow.grade() >= 50.0);
+    }
+
+    #[test]
+    fn test_sm2_intervals() {
+        let mut state = ReviewState::default();
+        let mut intervals = Vec::new();
+        for day in [0, 1, 7, 23] {
+            state.review(day, 5);
+            intervals.push(state.interval_days);
+        }
+        assert_eq!(intervals, [1, 6, 16, 45]);
+        assert_eq!(state.due_day, 23 + 45);
+
+        state.review(68, 1);
+        assert_eq!((state.repetitions, state.interval_days, state.due_day), (0, 1, 69));
+
+        for _ in 0..10 {
+            state.review(69, 0);
+        }
+        assert_eq!(state.ease, 1.3);
+    }
+
+    #[test]
+    fn test_review_quality_from_answers() {
+        let record = |input: Option<&str>, correct, seconds| AnswerRecord {
+            input: input.map(String::from),
+            correct,
+            elapsed: Duration::from_secs(seconds),
+        };
+        let quality = |record: &AnswerRecord| ReviewLog::from_answer(0, "bank", 1, record).quality;
+
+        assert_eq!(quality(&record(None, false, 30)), 0);
+        assert_eq!(quality(&record(Some("a"), false, 2)), 1);
+        assert_eq!(quality(&record(Some("b"), true, 2)), 5);
+        assert_eq!(quality(&record(Some("b"), true, 10)), 4);
+        assert_eq!(quality(&record(Some("b"), true, 60)), 3);
+    }
+
+    #[test]
+    fn test_study_history_round_trip() {
+        let path = std::env::temp_dir().join(format!("quiz-history-{}", std::process::id())).join("history.tsv");
+        let _ = fs::remove_file(&path);
+
+        let mut history = StudyHistory::load(&path).unwrap();
+        assert!(history.reviews.is_empty());
+        history.record(vec![
+            ReviewLog { day: 10, bank: "rust".to_string(), question_id: 1, quality: 5 },
+            ReviewLog { day: 10, bank: "rust".to_string(), question_id: 2, quality: 1 },
+        ]).unwrap();
+        history.record(vec![ReviewLog { day: 11, bank: "other".to_string(), question_id: 1, quality: 4 }]).unwrap();
+
+        let reloaded = StudyHistory::load(&path).unwrap();
+        assert_eq!(reloaded.reviews, history.reviews);
+        assert_eq!(reloaded.states("rust")[&1].due_day, 11);
+        assert_eq!(reloaded.states("other").len(), 1);
+        assert_eq!(reloaded.daily_progress("rust"), [DayProgress { day: 10, reviews: 2, correct: 1 }]);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        let error = StudyHistory::parse("# header\n10\trust\t1\t5\n10\trust\tone\t5\n").unwrap_err();
+        assert!(matches!(error, QuizError::InvalidHistory { line: 3, .. }));
+        assert!(StudyHistory::parse("10\trust\t1\t6\n").is_err());
+    }
+
+    #[test]
+    fn test_question_ids_survive_bank_edits() {
+        let ids = |contents: &str, format| -> Vec<usize> {
+            QuizBank::parse(contents, format).unwrap().questions.iter().map(QuizItem::id).collect()
+        };
+        let json = |questions: &[&str]| format!(r#"{{"questions": [{}]}}"#, questions.join(","));
+        let first = r#"{"id": 7, "text": "Rust is fast.", "type": "true_false", "answer": "true"}"#;
+        let second = r#"{"text": "Rust has a GC.", "type": "true_false", "answer": "false"}"#;
+        let inserted = r#"{"text": "Cargo builds crates.", "type": "true_false", "answer": "true"}"#;
+
+        let before = ids(&json(&[first, second]), BankFormat::Json);
+        let after = ids(&json(&[inserted, first, second]), BankFormat::Json);
+        assert_eq!(before[0], 7);
+        assert_eq!(&after[1..], before);
+        assert_ne!(after[0], before[1]);
+        assert!(after[0] < 1 << 53);
+
+        let markdown = "## Rust has a GC.\nId: 12\nType: true_false\nAnswer: false\n";
+        assert_eq!(ids(markdown, BankFormat::Markdown), [12]);
+        let toml = "[[questions]]\ntext = \"Rust has a GC.\"\ntype = \"true_false\"\nanswer = \"false\"\n";
+        assert_eq!(ids(toml, BankFormat::Toml), [before[1]]);
+    }
+
+    #[test]
+    fn test_study_order_puts_due_questions_first() {
+        let items: Vec<QuizItem> = (1..=15).map(|id| TrueFalseQuestion::new(id, "True?", true).into()).collect();
+        let mut states = HashMap::new();
+        for (id, due_day) in [(3, 12), (5, 8), (7, 20), (9, 10)] {
+            states.insert(id, ReviewState { due_day, ..ReviewState::default() });
+        }
+
+        let order: Vec<usize> = study_order(&items, &states, 12).iter().map(QuizItem::id).collect();
+        assert_eq!(&order[..3], [5, 9, 3]);
+        assert!(!order.contains(&7));
+        assert_eq!(&order[3..], [1, 2, 4, 6, 8, 10, 11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn test_question_counts_ignore_questions_outside_bank() {
+        let items: Vec<QuizItem> = (1..=4).map(|id| TrueFalseQuestion::new(id, "True?", true).into()).collect();
+        let mut states = HashMap::new();
+        for (id, interval_days) in [(2, 1), (4, 30), (7, 30), (8, 2), (9, 1)] {
+            states.insert(id, ReviewState { interval_days, ..ReviewState::default() });
+        }
+
+        assert_eq!(question_counts(&items, &states), (2, 1, 1));
+        assert_eq!(question_counts(&items[..1], &states), (1, 0, 0));
+    }
+
+    #[test]
+    fn test_format_day() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(11_016), "2000-02-29");
+        assert_eq!(format_day(20_744), "2026-10-18");
+    }
+
+    #[test]
+    fn test_seeded_shuffle_is_repeatable() {
+        let shuffled = |seed| {
+            let mut items: Vec<u32> = (0..20).collect();
+            SeededRng::new(seed).shuffle(&mut items);
+            items
+        };
+        assert_eq!(shuffled(42), shuffled(42));
+        assert_ne!(shuffled(42), shuffled(43));
+
+        let mut sorted = shuffled(42);
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shuffled_options_keep_answers_correct() {
+        let bank = QuizBank::parse(include_str!("data/quizzes/mixed-bag.toml"), BankFormat::Toml).unwrap();
+        let answers = ["2, 7", "false", "9.81", "Ferris", "Mercury, Earth, Mars, Jupiter", "loop"];
+        let QuizItem::Matching(matching) = &bank.questions[5] else { panic!("expected a matching question") };
+
+        for seed in 0..20 {
+            let mut rng = SeededRng::new(seed);
+            let questions = bank.questions.iter().enumerate().filter(|(i, _)| *i != 5).map(|(_, item)| item);
+            for (item, answer) in questions.zip(answers) {
+                let mut shuffled = item.clone();
+                shuffled.shuffle_options(&mut rng);
+                assert!(shuffled.check_answer(answer), "seed {}: {}", seed, shuffled.display());
+            }
+
+            // Every prompt still pairs with the same choice text.
+            let mut item = QuizItem::Matching(matching.clone());
+            item.shuffle_options(&mut rng);
+            let QuizItem::Matching(shuffled) = item else { unreachable!() };
+            for (i, prompt) in shuffled.prompts.iter().enumerate() {
+                let original = matching.prompts.iter().position(|p| p == prompt).unwrap();
+                assert_eq!(shuffled.choices[shuffled.pairs[i]], matching.choices[matching.pairs[original]]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_leaves_numbering_to_the_session() {
+        // Shuffled or pooled sessions ask bank ids out of order, so the session numbers questions itself.
+        let bank = QuizBank::parse(include_str!("data/quizzes/mixed-bag.toml"), BankFormat::Toml).unwrap();
+        for item in &bank.questions {
+            assert!(item.display().starts_with(item.text()), "{}", item.display());
+        }
+        assert_eq!(TrueFalseQuestion::new(7, "Rust has a garbage collector.", false).display(),
+                   "Rust has a garbage collector. (true/false)\n");
+    }
+
+    #[test]
+    fn test_tagged_pool_and_difficulty() {
+        let mut bank = QuizBank::parse(include_str!("data/quizzes/general-knowledge.json"), BankFormat::Json).unwrap();
+        assert_eq!(bank.difficulty(&bank.questions[7]), Difficulty::Hard);
+
+        bank.retain_tagged(&["Maths".to_string(), "science".to_string()]);
+        let ids: Vec<usize> = bank.questions.iter().map(QuizItem::id).collect();
+        assert_eq!(ids, [2, 3, 6, 8, 9]);
+
+        let error = QuizBank::parse("## Q\nDifficulty: brutal\n- [x] a\n- [ ] b\n", BankFormat::Markdown).unwrap_err();
+        assert!(error.contains("easy, medium or hard"), "{}", error);
+    }
+
+    #[test]
+    fn test_question_limit_draws_from_pool() {
+        let limits = TimeLimits::default();
+        let mut quiz = timed_session(limits, &[(0, "t"), (0, "t")]).with_question_limit(2);
+        quiz.execute().unwrap();
+
+        assert_eq!(quiz.questions.len(), 2);
+        assert_eq!(quiz.answers.len(), 2);
+        assert_eq!(quiz.grade(), 100.0);
+    }
+
+    #[test]
+    fn test_adaptive_difficulty_follows_answers() {
+        use Difficulty::*;
+        let clock = Rc::new(FakeClock::default());
+        let difficulties = [Easy, Easy, Medium, Medium, Hard, Hard];
+        let questions: Vec<QuizItem> = (1..=6).map(|id| TrueFalseQuestion::new(id, "True?", true).into()).collect();
+        // Right, right, wrong, wrong: medium, hard, hard, medium, easy.
+        let input = ScriptedAnswers { clock: clock.clone(), answers: [(0, "t"), (0, "t"), (0, "f"), (0, "f"), (0, "t")].into() };
+
+        let mut quiz = QuizSession::with_io(questions, clock, Box::new(input))
+            .with_question_limit(5)
+            .with_adaptive_difficulty(difficulties.to_vec());
+        quiz.execute().unwrap();
+
+        let asked: Vec<usize> = quiz.questions.iter().map(QuizItem::id).collect();
+        assert_eq!(asked, [3, 5, 6, 4, 1]);
+        assert_eq!(quiz.difficulties, [Medium, Hard, Hard, Medium, Easy]);
+        assert_eq!(quiz.score, 3);
+    }
+
+    fn sample_attempt(user: &str, attempt: usize, results: &[(usize, Option<&str>, bool)]) -> Attempt {
+        Attempt {
+            user: user.to_string(),
+            attempt,
+            timestamp: 1_792_351_808,
+            bank: "science".to_string(),
+            title: "Science".to_string(),
+            score: results.iter().filter(|(_, _, correct)| *correct).count(),
+            grade: 50.0,
+            answers: results.iter()
+                .map(|&(id, given, correct)| AttemptAnswer {
+                    question_id: id,
+                    question: format!("Question {}, \"quoted\"\nwith a second line", id),
+                    difficulty: Difficulty::Hard,
+                    given: given.map(String::from),
+                    correct_answer: "b) Au".to_string(),
+                    correct,
+                    seconds: 2.5,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_attempt_json_round_trip() {
+        let attempt = sample_attempt("alex", 3, &[(1, Some("b"), true), (2, None, false), (3, Some("tab\there"), false)]);
+        let line = attempt.to_json();
+        assert!(!line.contains('\n'));
+
+        let parsed = ResultStore::parse(&format!("{}\n\n{}\n", line, line)).unwrap();
+        assert_eq!(parsed, [attempt.clone(), attempt]);
+
+        let error = ResultStore::parse("{\"user\": \"alex\"}\n").unwrap_err();
+        assert!(matches!(error, QuizError::InvalidResults { line: 1, .. }));
+    }
+
+    #[test]
+    fn test_attempt_from_session_and_review() {
+        let bank = QuizBank::parse(include_str!("data/quizzes/general-knowledge.json"), BankFormat::Json).unwrap();
+        let clock = Rc::new(FakeClock::default());
+        let input = ScriptedAnswers { clock: clock.clone(), answers: [(3, "paris"), (1, "7")].into() };
+        let mut quiz = QuizSession::with_io(bank.questions.clone(), clock, Box::new(input)).with_question_limit(2);
+        quiz.execute().unwrap();
+
+        let attempt = Attempt::from_session("alex", 1, 0, &bank, &quiz);
+        assert_eq!(attempt.score, 1);
+        assert_eq!(attempt.answers[0].seconds, 3.0);
+        assert_eq!(attempt.answers[1].given.as_deref(), Some("7"));
+        assert_eq!(attempt.answers[1].difficulty, Difficulty::Easy);
+
+        let review = attempt.review();
+        assert!(review.contains("1. What is the capital of France?\n   Your answer: paris ✓\n2."));
+        assert!(review.contains("   Your answer: 7 ✗\n   Correct answer: b) 8\n"));
+        assert!(review.contains("Score: 1/2 (50.0%)"));
+        assert!(review.contains("1970-01-01 00:00 UTC"));
+    }
+
+    #[test]
+    fn test_attempts_are_numbered_per_user() {
+        let store = ResultStore {
+            path: PathBuf::new(),
+            attempts: vec![sample_attempt("alex", 1, &[]), sample_attempt("sam", 1, &[]), sample_attempt("alex", 2, &[])],
+        };
+
+        assert_eq!(store.next_attempt("alex"), 3);
+        assert_eq!(store.next_attempt("kim"), 1);
+        assert_eq!(store.find("alex", None).map(|a| a.attempt), Some(2));
+        assert_eq!(store.find("alex", Some(1)).map(|a| a.attempt), Some(1));
+        assert!(store.find("sam", Some(2)).is_none());
+    }
+
+    #[test]
+    fn test_question_stats_export() {
+        let attempts = [
+            sample_attempt("alex", 1, &[(1, Some("b"), true), (2, Some("a"), false)]),
+            sample_attempt("sam", 1, &[(1, Some("b"), true), (2, None, false)]),
+            sample_attempt("kim", 1, &[(1, Some("c"), false), (2, Some("b"), true)]),
+        ];
+        let stats = question_stats(&attempts);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].question_id, stats[0].attempts, stats[0].correct, stats[0].skipped), (2, 3, 1, 1));
+        assert_eq!(stats[0].observed_difficulty(), Difficulty::Hard);
+        assert_eq!(stats[1].observed_difficulty(), Difficulty::Medium);
+        assert_eq!(stats[1].average_seconds(), 2.5);
+
+        let mut csv = Vec::new();
+        export_csv(&stats, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("bank,question_id,question,"));
+        assert!(csv.contains("science,2,\"Question 2, \"\"quoted\"\"\nwith a second line\",hard,hard,3,1,1,0.333,2.5\n"));
+
+        let mut json = Vec::new();
+        export_json(&attempts, &stats, &mut json).unwrap();
+        let json = JsonParser::parse(std::str::from_utf8(&json).unwrap()).unwrap();
+        let JsonValue::Array(questions) = json.get("questions").unwrap() else { panic!("questions should be an array") };
+        assert_eq!(questions[0].get("p_value").and_then(JsonValue::as_f64), Some(0.333));
+        assert_eq!(questions[1].get("observed_difficulty").and_then(JsonValue::as_str), Some("medium"));
+    }
+
+    #[test]
+    fn test_question_stats_follow_questions_across_bank_edits() {
+        let attempt = |texts: &[&str], number| {
+            let questions: Vec<String> = texts.iter()
+                .map(|text| format!(r#"{{"text": "{}", "type": "true_false", "answer": "true"}}"#, text))
+                .collect();
+            let bank = QuizBank::parse(&format!(r#"{{"questions": [{}]}}"#, questions.join(",")), BankFormat::Json).unwrap();
+            let clock = Rc::new(FakeClock::default());
+            let input = ScriptedAnswers { clock: clock.clone(), answers: texts.iter().map(|_| (1, "true")).collect() };
+            let mut quiz = QuizSession::with_io(bank.questions.clone(), clock, Box::new(input));
+            quiz.execute().unwrap();
+            // Read back from the results file format, as the export does.
+            ResultStore::parse(&Attempt::from_session("alex", number, 0, &bank, &quiz).to_json()).unwrap().remove(0)
+        };
+        let attempts = [
+            attempt(&["Rust is fast.", "Cargo builds crates."], 1),
+            attempt(&["Rust is safe.", "Rust is fast.", "Cargo builds crates."], 2),
+        ];
+
+        let stats = question_stats(&attempts);
+        let attempts_for = |text: &str| stats.iter().filter(|s| s.question == text).map(|s| s.attempts).collect::<Vec<_>>();
+        assert_eq!(stats.len(), 3);
+        assert_eq!(attempts_for("Rust is fast."), [2]);
+        assert_eq!(attempts_for("Cargo builds crates."), [2]);
+        assert_eq!(attempts_for("Rust is safe."), [1]);
     }
 }